@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SerumGovError;
+use crate::state::RedeemTicket;
+use crate::MSRM_MULTIPLIER;
+
+/// Amount of SRM or MSRM to be redeemed for burning `gsrm_amount`.
+/// CHECK: gSRM from MSRM positions can only be burned in multiples of MSRM_MULTIPLIER.
+pub fn redeem_amount(gsrm_amount: u64, is_msrm: bool) -> Result<u64> {
+    if !is_msrm {
+        return Ok(gsrm_amount);
+    }
+
+    if gsrm_amount.checked_rem(MSRM_MULTIPLIER) != Some(0) {
+        return err!(SerumGovError::InvalidMSRMAmount);
+    }
+
+    Ok(gsrm_amount
+        .checked_div(MSRM_MULTIPLIER)
+        .ok_or(SerumGovError::MathOverflow)?)
+}
+
+/// CHECK: RedeemTicket must be worth exactly the gSRM burned for it.
+pub fn check_burn(gsrm_amount: u64, redeem_ticket: &RedeemTicket) -> Result<()> {
+    let redeemed_gsrm = if redeem_ticket.is_msrm {
+        redeem_ticket.amount.checked_mul(MSRM_MULTIPLIER)
+    } else {
+        Some(redeem_ticket.amount)
+    };
+
+    if gsrm_amount == 0 || redeemed_gsrm != Some(gsrm_amount) {
+        return err!(SerumGovError::ConservationViolated);
+    }
+
+    Ok(())
+}
+
+/// CHECK: Vault must have sent out exactly the RedeemTicket amount.
+pub fn check_redeem(vault_amount_before: u64, vault_amount_after: u64, amount: u64) -> Result<()> {
+    if vault_amount_before.checked_sub(vault_amount_after) != Some(amount) {
+        return err!(SerumGovError::ConservationViolated);
+    }
+
+    Ok(())
+}
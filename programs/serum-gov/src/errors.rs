@@ -22,4 +22,103 @@ pub enum SerumGovError {
 
     #[msg("Already redeemed account.")]
     AlreadyRedeemed,
+
+    #[msg("Account is not a LockedAccount or VestAccount.")]
+    InvalidPositionAccount,
+
+    #[msg("Position was passed in more than once.")]
+    DuplicatePositionAccount,
+
+    #[msg("Invalid owner for position.")]
+    InvalidPositionOwner,
+
+    #[msg("Invalid lockup end.")]
+    InvalidLockupEnd,
+
+    #[msg("Lockup hasn't ended yet.")]
+    LockupNotEnded,
+
+    #[msg("Invalid lockup config.")]
+    InvalidLockupConfig,
+
+    #[msg("Invalid delegate for user.")]
+    InvalidDelegate,
+
+    #[msg("Timestamp is older than the stored checkpoints.")]
+    CheckpointNotFound,
+
+    #[msg("Invalid min position age.")]
+    InvalidMinPositionAge,
+
+    #[msg("Invalid TokenOwnerRecord.")]
+    InvalidTokenOwnerRecord,
+
+    #[msg("Owner has unrelinquished votes in SPL Governance.")]
+    ActiveVotes,
+
+    #[msg("Account can't be migrated.")]
+    InvalidMigrationAccount,
+
+    #[msg("Invalid vest weight config.")]
+    InvalidVestWeightConfig,
+
+    #[msg("Account doesn't need migrating.")]
+    AlreadyMigrated,
+
+    #[msg("Invalid weight curve config.")]
+    InvalidWeightCurveConfig,
+
+    #[msg("Vaults don't cover recorded liabilities.")]
+    VaultShortfall,
+
+    #[msg("Stats totals have drifted from positions and tickets.")]
+    StatsDrift,
+
+    #[msg("Burned gSRM and redeemed amount don't match.")]
+    ConservationViolated,
+
+    #[msg("Position isn't active.")]
+    PositionNotActive,
+
+    #[msg("Position still has gSRM to burn.")]
+    PositionNotFullyBurned,
+
+    #[msg("Position has unsettled tickets.")]
+    UnsettledTickets,
+
+    #[msg("Arithmetic overflow.")]
+    MathOverflow,
+
+    #[msg("Arithmetic underflow.")]
+    MathUnderflow,
+
+    #[msg("Amount doesn't fit in a u64.")]
+    AmountTooLarge,
+
+    #[msg("Elapsed time can't be negative.")]
+    NegativeElapsedTime,
+
+    #[msg("Delays and periods can't be negative.")]
+    NegativeDuration,
+
+    #[msg("Linear vesting period must be greater than 0.")]
+    ZeroVestingPeriod,
+
+    #[msg("Rent must be refunded to the account which paid it.")]
+    InvalidRentPayer,
+
+    #[msg("User still has positions which aren't closed.")]
+    OpenPositions,
+
+    #[msg("Lock tier doesn't exist.")]
+    InvalidLockTier,
+
+    #[msg("Invalid lock tiers.")]
+    InvalidLockTiers,
+
+    #[msg("Positions must be for the same asset and lock tier.")]
+    IncompatiblePositions,
+
+    #[msg("Max voter weight is only known under the Linear weight curve.")]
+    NonLinearWeightCurve,
 }
@@ -0,0 +1,282 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{LockTier, VestWeightMode, WeightCurve};
+
+#[event]
+pub struct InitEvent {
+    pub config_authority: Pubkey,
+    pub gsrm_mint: Pubkey,
+    pub srm_mint: Pubkey,
+    pub msrm_mint: Pubkey,
+    pub claim_delay: i64,
+    pub redeem_delay: i64,
+    pub cliff_period: i64,
+    pub linear_vesting_period: i64,
+}
+
+/// Emitted by `init_stats` with the seeded totals.
+#[event]
+pub struct InitStatsEvent {
+    pub srm_locked: u64,
+    pub msrm_locked: u64,
+    pub srm_vesting: u64,
+    pub msrm_vesting: u64,
+    pub gsrm_unclaimed: u64,
+    pub srm_redeemable: u64,
+    pub msrm_redeemable: u64,
+}
+
+#[event]
+pub struct UpdateConfigAuthorityEvent {
+    pub old_config_authority: Pubkey,
+    pub new_config_authority: Pubkey,
+}
+
+#[event]
+pub struct UpdateConfigParamsEvent {
+    pub claim_delay: i64,
+    pub redeem_delay: i64,
+    pub cliff_period: i64,
+    pub linear_vesting_period: i64,
+}
+
+#[event]
+pub struct UpdateLockupConfigEvent {
+    pub max_lockup_period: i64,
+    pub max_lockup_multiplier: u64,
+}
+
+#[event]
+pub struct UpdateVestWeightConfigEvent {
+    pub vest_weight_mode: VestWeightMode,
+    pub unvested_weight: u64,
+}
+
+#[event]
+pub struct UpdateGovernanceConfigEvent {
+    pub governance_program: Pubkey,
+    pub realm: Pubkey,
+    pub governing_token_mint: Pubkey,
+}
+
+#[event]
+pub struct UpdateMinPositionAgeEvent {
+    pub min_position_age: i64,
+}
+
+#[event]
+pub struct UpdateWeightCurveEvent {
+    pub weight_curve: WeightCurve,
+    pub max_owner_weight: u64,
+}
+
+#[event]
+pub struct UpdateLockTiersEvent {
+    pub lock_tiers: Vec<LockTier>,
+    pub max_lock_multiplier: u64,
+}
+
+#[event]
+pub struct MigrateAccountEvent {
+    pub account: Pubkey,
+    pub old_len: u64,
+    pub new_len: u64,
+}
+
+#[event]
+pub struct InitUserEvent {
+    pub owner: Pubkey,
+    pub user_account: Pubkey,
+}
+
+#[event]
+pub struct CloseUserEvent {
+    pub owner: Pubkey,
+    pub user_account: Pubkey,
+    pub lock_index: u64,
+    pub vest_index: u64,
+}
+
+#[event]
+pub struct DelegateEvent {
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
+}
+
+#[event]
+pub struct UndelegateEvent {
+    pub owner: Pubkey,
+    pub old_delegate: Pubkey,
+}
+
+/// Emitted by `deposit_locked_srm` and `deposit_locked_msrm`.
+#[event]
+pub struct DepositLockedEvent {
+    pub owner: Pubkey,
+    pub locked_account: Pubkey,
+    pub claim_ticket: Pubkey,
+    pub lock_index: u64,
+    pub is_msrm: bool,
+    /// Amount of SRM or MSRM deposited.
+    pub amount: u64,
+    pub gsrm_amount: u64,
+    pub lockup_end: i64,
+    /// NOTE: 0 if no lock tier was picked.
+    pub lock_tier: u8,
+    pub timestamp: i64,
+}
+
+/// Emitted by `increase_locked_srm` and `increase_locked_msrm`.
+#[event]
+pub struct IncreaseLockedEvent {
+    pub owner: Pubkey,
+    pub locked_account: Pubkey,
+    pub claim_ticket: Pubkey,
+    pub lock_index: u64,
+    pub is_msrm: bool,
+    /// Amount of SRM or MSRM added.
+    pub amount: u64,
+    pub gsrm_amount: u64,
+    pub total_gsrm_amount: u64,
+    pub lockup_end: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ExtendLockEvent {
+    pub owner: Pubkey,
+    pub locked_account: Pubkey,
+    pub lock_index: u64,
+    pub old_lockup_end: i64,
+    pub new_lockup_end: i64,
+    /// Voting power of the LockedAccount with the new lockup end.
+    pub voting_power: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted by `deposit_vest_srm` and `deposit_vest_msrm`.
+#[event]
+pub struct DepositVestEvent {
+    pub owner: Pubkey,
+    pub vest_account: Pubkey,
+    pub claim_ticket: Pubkey,
+    pub vest_index: u64,
+    pub is_msrm: bool,
+    /// Amount of SRM or MSRM deposited.
+    pub amount: u64,
+    pub gsrm_amount: u64,
+    pub cliff_period: i64,
+    pub linear_vesting_period: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ClaimEvent {
+    pub owner: Pubkey,
+    pub claim_ticket: Pubkey,
+    pub deposit_account: Pubkey,
+    pub gsrm_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BurnLockedEvent {
+    pub owner: Pubkey,
+    pub locked_account: Pubkey,
+    pub redeem_ticket: Pubkey,
+    pub redeem_index: u64,
+    pub is_msrm: bool,
+    pub gsrm_amount: u64,
+    /// Amount of SRM or MSRM to be redeemed.
+    pub redeem_amount: u64,
+    pub gsrm_burned: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BurnVestEvent {
+    pub owner: Pubkey,
+    pub vest_account: Pubkey,
+    pub redeem_ticket: Pubkey,
+    pub redeem_index: u64,
+    pub is_msrm: bool,
+    pub gsrm_amount: u64,
+    /// Amount of SRM or MSRM to be redeemed.
+    pub redeem_amount: u64,
+    pub gsrm_burned: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted by `redeem_srm` and `redeem_msrm`.
+#[event]
+pub struct RedeemEvent {
+    pub owner: Pubkey,
+    pub redeem_ticket: Pubkey,
+    pub deposit_account: Pubkey,
+    pub redeem_index: u64,
+    pub is_msrm: bool,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ClosePositionEvent {
+    pub owner: Pubkey,
+    pub position: Pubkey,
+    pub rent_payer: Pubkey,
+    pub is_msrm: bool,
+    pub total_gsrm_amount: u64,
+}
+
+#[event]
+pub struct MergeLockedAccountsEvent {
+    pub owner: Pubkey,
+    pub locked_account: Pubkey,
+    /// LockedAccounts merged in and closed.
+    pub merged: Vec<Pubkey>,
+    pub total_gsrm_amount: u64,
+    pub gsrm_burned: u64,
+    pub lockup_end: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SplitLockedAccountEvent {
+    pub owner: Pubkey,
+    pub locked_account: Pubkey,
+    pub new_locked_account: Pubkey,
+    pub new_lock_index: u64,
+    pub is_msrm: bool,
+    /// Unburned gSRM moved to the new LockedAccount.
+    pub gsrm_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuditVaultsEvent {
+    pub srm_vault_amount: u64,
+    pub srm_liabilities: u64,
+    pub msrm_vault_amount: u64,
+    pub msrm_liabilities: u64,
+    pub is_solvent: bool,
+    /// Decreases which took a total in Stats below zero since it was last seeded.
+    pub stats_underflows: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VoterWeightRecordEvent {
+    pub voter_weight_record: Pubkey,
+    pub realm: Pubkey,
+    pub governing_token_mint: Pubkey,
+    pub governing_token_owner: Pubkey,
+    pub voter_weight: u64,
+}
+
+#[event]
+pub struct MaxVoterWeightRecordEvent {
+    pub max_voter_weight_record: Pubkey,
+    pub realm: Pubkey,
+    pub governing_token_mint: Pubkey,
+    pub max_voter_weight: u64,
+}
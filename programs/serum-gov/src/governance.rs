@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SerumGovError;
+use crate::state::Config;
+
+/// GovernanceAccountType of TokenOwnerRecordV1 and TokenOwnerRecordV2 in SPL Governance.
+const TOKEN_OWNER_RECORD_ACCOUNT_TYPES: [u8; 2] = [2, 17];
+
+/// Offset of `unrelinquished_votes_count` in TokenOwnerRecord.
+/// account_type + realm + governing_token_mint + governing_token_owner + governing_token_deposit_amount
+const UNRELINQUISHED_VOTES_COUNT_OFFSET: usize = 1 + 32 + 32 + 32 + 8;
+
+/// CHECK: None of `voters` may have unrelinquished votes in the configured realm.
+/// NOTE: TokenOwnerRecords of `voters` must lead `remaining_accounts` in the same order, if enabled in Config.
+pub fn check_no_active_votes(
+    config: &Config,
+    voters: &[Pubkey],
+    remaining_accounts: &[AccountInfo],
+) -> Result<()> {
+    if config.governance_program == Pubkey::default() {
+        return Ok(());
+    }
+
+    if remaining_accounts.len() < voters.len() {
+        return err!(SerumGovError::InvalidTokenOwnerRecord);
+    }
+
+    for (voter, token_owner_record) in voters.iter().zip(remaining_accounts) {
+        check_token_owner_record(config, voter, token_owner_record)?;
+    }
+
+    Ok(())
+}
+
+fn check_token_owner_record(
+    config: &Config,
+    voter: &Pubkey,
+    token_owner_record: &AccountInfo,
+) -> Result<()> {
+    let (token_owner_record_address, _) = Pubkey::find_program_address(
+        &[
+            b"governance",
+            &config.realm.to_bytes()[..],
+            &config.governing_token_mint.to_bytes()[..],
+            &voter.to_bytes()[..],
+        ],
+        &config.governance_program,
+    );
+
+    if token_owner_record.key() != token_owner_record_address {
+        return err!(SerumGovError::InvalidTokenOwnerRecord);
+    }
+
+    // Voter has never deposited in the realm, so can't have voted either.
+    if token_owner_record.data_is_empty() {
+        return Ok(());
+    }
+
+    if token_owner_record.owner != &config.governance_program {
+        return err!(SerumGovError::InvalidTokenOwnerRecord);
+    }
+
+    let data = token_owner_record.try_borrow_data()?;
+    if data.len() < UNRELINQUISHED_VOTES_COUNT_OFFSET + 4
+        || !TOKEN_OWNER_RECORD_ACCOUNT_TYPES.contains(&data[0])
+    {
+        return err!(SerumGovError::InvalidTokenOwnerRecord);
+    }
+
+    // NOTE: Only the lower 4 bytes are read, since the field was widened from u32 to u64 in later versions.
+    let unrelinquished_votes_count = u32::from_le_bytes(
+        data[UNRELINQUISHED_VOTES_COUNT_OFFSET..UNRELINQUISHED_VOTES_COUNT_OFFSET + 4]
+            .try_into()
+            .map_err(|_| SerumGovError::InvalidTokenOwnerRecord)?,
+    );
+    msg!("Unrelinquished votes: {}", unrelinquished_votes_count);
+
+    if unrelinquished_votes_count > 0 {
+        return err!(SerumGovError::ActiveVotes);
+    }
+
+    Ok(())
+}
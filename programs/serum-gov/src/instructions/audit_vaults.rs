@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::errors::SerumGovError;
+use crate::events::AuditVaultsEvent;
+use crate::state::{Config, Stats};
+
+/// Checks that the vaults hold enough SRM and MSRM to cover all positions and RedeemTickets,
+/// and that the totals in Stats haven't drifted.
+/// NOTE: Permissionless, so keepers can run it continuously.
+#[derive(Accounts)]
+pub struct AuditVaults<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [b"stats"],
+        bump = stats.bump
+    )]
+    pub stats: Account<'info, Stats>,
+
+    #[account(
+        seeds = [b"vault", &config.srm_mint.to_bytes()[..]],
+        bump,
+    )]
+    pub srm_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"vault", &config.msrm_mint.to_bytes()[..]],
+        bump,
+    )]
+    pub msrm_vault: Account<'info, TokenAccount>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+/// Fails on a shortfall or drift if `strict`, otherwise only reports it in the emitted event.
+pub fn handler(ctx: Context<AuditVaults>, strict: bool) -> Result<()> {
+    let stats = &ctx.accounts.stats;
+
+    let srm_vault_amount = ctx.accounts.srm_vault.amount;
+    let srm_liabilities = stats.srm_liabilities()?;
+    let msrm_vault_amount = ctx.accounts.msrm_vault.amount;
+    let msrm_liabilities = stats.msrm_liabilities()?;
+
+    // NOTE: Vaults can hold more than needed, since anyone can transfer tokens into them.
+    let is_solvent = srm_vault_amount >= srm_liabilities && msrm_vault_amount >= msrm_liabilities;
+
+    msg!(
+        "SRM vault: {:?}, MSRM vault: {:?}",
+        (srm_vault_amount, srm_liabilities),
+        (msrm_vault_amount, msrm_liabilities)
+    );
+
+    // NOTE: Totals only ever go below zero when they were undercounted, e.g. when seeded too low.
+    let stats_underflows = stats.underflows;
+    msg!("Stats underflows: {}", stats_underflows);
+
+    if strict && !is_solvent {
+        return err!(SerumGovError::VaultShortfall);
+    }
+
+    if strict && stats_underflows > 0 {
+        return err!(SerumGovError::StatsDrift);
+    }
+
+    emit!(AuditVaultsEvent {
+        srm_vault_amount,
+        srm_liabilities,
+        msrm_vault_amount,
+        msrm_liabilities,
+        is_solvent,
+        stats_underflows,
+        timestamp: ctx.accounts.clock.unix_timestamp,
+    });
+
+    Ok(())
+}
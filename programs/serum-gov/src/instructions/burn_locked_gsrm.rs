@@ -1,10 +1,17 @@
-use anchor_lang::{prelude::*, AccountsClose};
+use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount};
 
+use crate::conservation::{check_burn, redeem_amount};
 use crate::errors::*;
-use crate::state::{Config, LockedAccount, RedeemTicket};
-use crate::MSRM_MULTIPLIER;
-
+use crate::events::BurnLockedEvent;
+use crate::governance::check_no_active_votes;
+use crate::state::{
+    Checkpoints, Config, LockedAccount, PositionActionKind, PositionHistory, PositionStatus,
+    RedeemTicket, Stats, User,
+};
+
+/// NOTE: TokenOwnerRecords of the owner and of their delegate, if any, must be passed in
+/// as `remaining_accounts` if checked in Config, since votes cast with the positions can sit on either.
 #[derive(Accounts)]
 pub struct BurnLockedGSRM<'info> {
     #[account(mut)]
@@ -23,6 +30,22 @@ pub struct BurnLockedGSRM<'info> {
     )]
     pub config: Account<'info, Config>,
 
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [b"stats"],
+        bump,
+        space = Stats::LEN
+    )]
+    pub stats: Box<Account<'info, Stats>>,
+
+    #[account(
+        mut,
+        seeds = [b"user", &owner.key().to_bytes()[..]],
+        bump,
+    )]
+    pub owner_user_account: Box<Account<'info, User>>,
+
     #[account(
         mut,
         seeds = [b"gSRM"],
@@ -43,9 +66,20 @@ pub struct BurnLockedGSRM<'info> {
         mut,
         seeds = [b"locked_account", &owner.key().to_bytes()[..], locked_account.lock_index.to_le_bytes().as_ref()],
         bump,
+        constraint = clock.unix_timestamp >= locked_account.lockup_end @ SerumGovError::LockupNotEnded,
+        constraint = locked_account.status == PositionStatus::Active @ SerumGovError::PositionNotActive,
     )]
     pub locked_account: Account<'info, LockedAccount>,
 
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [b"checkpoints", &owner.key().to_bytes()[..]],
+        bump,
+        space = Checkpoints::LEN
+    )]
+    pub owner_checkpoints: Box<Account<'info, Checkpoints>>,
+
     #[account(
         init,
         payer = owner,
@@ -55,6 +89,15 @@ pub struct BurnLockedGSRM<'info> {
     )]
     pub redeem_ticket: Account<'info, RedeemTicket>,
 
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [b"position_history", &locked_account.key().to_bytes()[..]],
+        bump,
+        space = PositionHistory::LEN
+    )]
+    pub position_history: Box<Account<'info, PositionHistory>>,
+
     pub clock: Sysvar<'info, Clock>,
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
@@ -77,6 +120,12 @@ pub fn handler(ctx: Context<BurnLockedGSRM>, amount: u64) -> Result<()> {
         return Err(ProgramError::InvalidInstructionData.into());
     }
 
+    check_no_active_votes(
+        &ctx.accounts.config,
+        &ctx.accounts.owner_user_account.voters(),
+        ctx.remaining_accounts,
+    )?;
+
     // Doesn't matter if placed here, or below since txs are atomic.
     token::burn(
         ctx.accounts
@@ -88,30 +137,24 @@ pub fn handler(ctx: Context<BurnLockedGSRM>, amount: u64) -> Result<()> {
     let locked_account = &mut ctx.accounts.locked_account;
 
     // CHECK: User can only burn gSRM tokens received from this LockedAccount.
-    if amount + locked_account.gsrm_burned > locked_account.total_gsrm_amount {
+    let gsrm_burned = amount
+        .checked_add(locked_account.gsrm_burned)
+        .ok_or(SerumGovError::MathOverflow)?;
+    if gsrm_burned > locked_account.total_gsrm_amount {
         return err!(SerumGovError::InvalidGSRMAmount);
     }
 
-    // CHECK: Amount must be multiple of MSRM_MULTIPLIER if LockedAccount was created on depositing MSRM tokens.
-    if locked_account.is_msrm && (amount % MSRM_MULTIPLIER != 0) {
-        return err!(SerumGovError::InvalidMSRMAmount);
-    }
+    // Calculating amount of SRM or MSRM tokens to be redeemed back.
+    // NOTE: User cannot redeem SRM from a MSRM LockedAccount.
+    let redeem_amount = redeem_amount(amount, locked_account.is_msrm)?;
 
-    locked_account.gsrm_burned = locked_account.gsrm_burned.checked_add(amount).unwrap();
+    locked_account.gsrm_burned = gsrm_burned;
 
-    // Closing LockedAccount if all gSRM tokens were burned.
+    // NOTE: LockedAccount is kept around, it's closed by `close_position` once its tickets are settled.
     if locked_account.gsrm_burned == locked_account.total_gsrm_amount {
-        locked_account.close(ctx.accounts.owner.to_account_info())?;
+        locked_account.status = PositionStatus::FullyBurned;
     }
 
-    // Calculating amount of SRM or gSRM tokens to be redeemed back.
-    // NOTE: User cannot redeem SRM from a MSRM LockedAccount.
-    let redeem_amount = if locked_account.is_msrm {
-        amount / MSRM_MULTIPLIER
-    } else {
-        amount
-    };
-
     let redeem_ticket = &mut ctx.accounts.redeem_ticket;
     redeem_ticket.owner = ctx.accounts.owner.key();
     redeem_ticket.deposit_account = locked_account.key();
@@ -122,7 +165,54 @@ pub fn handler(ctx: Context<BurnLockedGSRM>, amount: u64) -> Result<()> {
     redeem_ticket.redeem_delay = ctx.accounts.config.redeem_delay;
     redeem_ticket.amount = redeem_amount;
 
-    locked_account.redeem_index = locked_account.redeem_index.checked_add(1).unwrap();
+    check_burn(amount, redeem_ticket)?;
+
+    locked_account.redeem_index = locked_account
+        .redeem_index
+        .checked_add(1)
+        .ok_or(SerumGovError::MathOverflow)?;
+
+    let is_msrm = locked_account.is_msrm;
+
+    let stats = &mut ctx.accounts.stats;
+    stats.initialize(*ctx.bumps.get("stats").unwrap());
+    stats.record_burn(false, is_msrm, redeem_amount);
+
+    ctx.accounts
+        .owner_user_account
+        .record_burn(false, is_msrm, redeem_amount, amount);
+
+    let checkpoints = &mut ctx.accounts.owner_checkpoints;
+    checkpoints.initialize_untracked(
+        ctx.accounts.owner.key(),
+        *ctx.bumps.get("owner_checkpoints").unwrap(),
+        ctx.accounts.owner.key(),
+    );
+    checkpoints.decrease(amount, ctx.accounts.clock.unix_timestamp)?;
+
+    let position_history = &mut ctx.accounts.position_history;
+    position_history.initialize(
+        ctx.accounts.locked_account.key(),
+        *ctx.bumps.get("position_history").unwrap(),
+    );
+    position_history.record(
+        PositionActionKind::Burn,
+        amount,
+        ctx.accounts.redeem_ticket.key(),
+        ctx.accounts.clock.unix_timestamp,
+    )?;
+
+    emit!(BurnLockedEvent {
+        owner: ctx.accounts.owner.key(),
+        locked_account: ctx.accounts.locked_account.key(),
+        redeem_ticket: ctx.accounts.redeem_ticket.key(),
+        redeem_index: ctx.accounts.redeem_ticket.redeem_index,
+        is_msrm: ctx.accounts.locked_account.is_msrm,
+        gsrm_amount: amount,
+        redeem_amount,
+        gsrm_burned: ctx.accounts.locked_account.gsrm_burned,
+        timestamp: ctx.accounts.clock.unix_timestamp,
+    });
 
     Ok(())
 }
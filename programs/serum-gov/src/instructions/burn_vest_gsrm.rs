@@ -1,14 +1,22 @@
 use std::cmp;
 
-use anchor_lang::{prelude::*, AccountsClose};
+use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount};
 
 use crate::{
+    conservation::{check_burn, redeem_amount},
     errors::SerumGovError,
-    state::{Config, RedeemTicket, VestAccount},
+    events::BurnVestEvent,
+    governance::check_no_active_votes,
+    state::{
+        Checkpoints, Config, PositionActionKind, PositionHistory, PositionStatus, RedeemTicket,
+        Stats, User, VestAccount,
+    },
     MSRM_MULTIPLIER,
 };
 
+/// NOTE: TokenOwnerRecords of the owner and of their delegate, if any, must be passed in
+/// as `remaining_accounts` if checked in Config, since votes cast with the positions can sit on either.
 #[derive(Accounts)]
 pub struct BurnVestGSRM<'info> {
     #[account(mut)]
@@ -27,6 +35,22 @@ pub struct BurnVestGSRM<'info> {
     )]
     pub config: Account<'info, Config>,
 
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [b"stats"],
+        bump,
+        space = Stats::LEN
+    )]
+    pub stats: Box<Account<'info, Stats>>,
+
+    #[account(
+        mut,
+        seeds = [b"user", &owner.key().to_bytes()[..]],
+        bump,
+    )]
+    pub owner_user_account: Box<Account<'info, User>>,
+
     #[account(
         mut,
         seeds = [b"gSRM"],
@@ -47,10 +71,20 @@ pub struct BurnVestGSRM<'info> {
         mut,
         seeds = [b"vest_account", &owner.key().to_bytes()[..], vest_account.vest_index.to_le_bytes().as_ref()],
         bump,
-        constraint = clock.unix_timestamp >= (vest_account.created_at + vest_account.cliff_period) @ SerumGovError::TooEarlyToVest,
+        constraint = clock.unix_timestamp >= vest_account.created_at.checked_add(vest_account.cliff_period).ok_or(SerumGovError::MathOverflow)? @ SerumGovError::TooEarlyToVest,
+        constraint = vest_account.status == PositionStatus::Active @ SerumGovError::PositionNotActive,
     )]
     pub vest_account: Account<'info, VestAccount>,
 
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [b"checkpoints", &owner.key().to_bytes()[..]],
+        bump,
+        space = Checkpoints::LEN
+    )]
+    pub owner_checkpoints: Box<Account<'info, Checkpoints>>,
+
     #[account(
         init,
         payer = owner,
@@ -60,6 +94,15 @@ pub struct BurnVestGSRM<'info> {
     )]
     pub redeem_ticket: Account<'info, RedeemTicket>,
 
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [b"position_history", &vest_account.key().to_bytes()[..]],
+        bump,
+        space = PositionHistory::LEN
+    )]
+    pub position_history: Box<Account<'info, PositionHistory>>,
+
     pub clock: Sysvar<'info, Clock>,
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
@@ -82,34 +125,21 @@ pub fn handler(ctx: Context<BurnVestGSRM>, amount: u64) -> Result<()> {
         return Err(ProgramError::InvalidInstructionData.into());
     }
 
+    check_no_active_votes(
+        &ctx.accounts.config,
+        &ctx.accounts.owner_user_account.voters(),
+        ctx.remaining_accounts,
+    )?;
+
     let vest_account = &ctx.accounts.vest_account;
 
-    let current_timestamp = ctx.accounts.clock.unix_timestamp;
-    let cliff_end = vest_account
-        .created_at
-        .checked_add(vest_account.cliff_period)
-        .unwrap();
-
-    // vested_time = time passed after cliff period ended
-    let vested_time = current_timestamp.checked_sub(cliff_end).unwrap();
-    msg!("Time vested: {}", vested_time);
-
-    // vested_amount = (vested_time / linear_vest_period) * total_gsrm_amount
-    let vested_amount = u128::from(vest_account.total_gsrm_amount)
-        .checked_mul(vested_time.try_into().unwrap())
-        .unwrap()
-        .checked_div(vest_account.linear_vesting_period.try_into().unwrap())
-        .unwrap();
-
-    // If vested_time > linear_vest_period, vested_amount will be greater than total_gsrm_amount.
-    // Hence, vested_amount = min(vested_amount, total_gsrm_amount)
-    let vested_amount = cmp::min(
-        u64::try_from(vested_amount).unwrap(),
-        vest_account.total_gsrm_amount,
-    );
+    let vested_amount = vest_account.vested_amount(ctx.accounts.clock.unix_timestamp)?;
+    msg!("Amount vested: {}", vested_amount);
 
     // Accounting for already redeemed gsrm
-    let redeemable_amount = vested_amount.checked_sub(vest_account.gsrm_burned).unwrap();
+    let redeemable_amount = vested_amount
+        .checked_sub(vest_account.gsrm_burned)
+        .ok_or(SerumGovError::MathUnderflow)?;
 
     // Just another layer of check if closing account remains vulnerable.
     if redeemable_amount <= 0 {
@@ -123,7 +153,18 @@ pub fn handler(ctx: Context<BurnVestGSRM>, amount: u64) -> Result<()> {
 
     // If user passed in amount < redeemable_amount, then redeem only that amount
     // gsrm_amount = min(redeemable_amount, amount)
-    let gsrm_amount = cmp::min(redeemable_amount, amount);
+    let mut gsrm_amount = cmp::min(redeemable_amount, amount);
+
+    // Only whole MSRM can be redeemed, so the rest stays in the VestAccount.
+    if vest_account.is_msrm {
+        gsrm_amount -= gsrm_amount % MSRM_MULTIPLIER;
+        if gsrm_amount == 0 {
+            return err!(SerumGovError::InvalidMSRMAmount);
+        }
+    }
+
+    // NOTE: Redeem amount always comes from the burned amount, never the requested one.
+    let redeem_amount = redeem_amount(gsrm_amount, vest_account.is_msrm)?;
 
     msg!("Redeeming {} gSRM", gsrm_amount);
     token::burn(
@@ -134,10 +175,14 @@ pub fn handler(ctx: Context<BurnVestGSRM>, amount: u64) -> Result<()> {
     )?;
 
     let vest_account = &mut ctx.accounts.vest_account;
-    vest_account.gsrm_burned = vest_account.gsrm_burned.checked_add(gsrm_amount).unwrap();
+    vest_account.gsrm_burned = vest_account
+        .gsrm_burned
+        .checked_add(gsrm_amount)
+        .ok_or(SerumGovError::MathOverflow)?;
 
+    // NOTE: VestAccount is kept around, it's closed by `close_position` once its tickets are settled.
     if vest_account.gsrm_burned == vest_account.total_gsrm_amount {
-        vest_account.close(ctx.accounts.owner.to_account_info())?;
+        vest_account.status = PositionStatus::FullyBurned;
     }
 
     let redeem_ticket = &mut ctx.accounts.redeem_ticket;
@@ -148,13 +193,56 @@ pub fn handler(ctx: Context<BurnVestGSRM>, amount: u64) -> Result<()> {
     redeem_ticket.is_msrm = vest_account.is_msrm;
     redeem_ticket.created_at = ctx.accounts.clock.unix_timestamp;
     redeem_ticket.redeem_delay = ctx.accounts.config.redeem_delay;
-    redeem_ticket.amount = if vest_account.is_msrm {
-        amount.checked_div(MSRM_MULTIPLIER).unwrap()
-    } else {
-        amount
-    };
+    redeem_ticket.amount = redeem_amount;
+
+    check_burn(gsrm_amount, redeem_ticket)?;
+
+    vest_account.redeem_index = vest_account
+        .redeem_index
+        .checked_add(1)
+        .ok_or(SerumGovError::MathOverflow)?;
+
+    let is_msrm = vest_account.is_msrm;
 
-    vest_account.redeem_index = vest_account.redeem_index.checked_add(1).unwrap();
+    let stats = &mut ctx.accounts.stats;
+    stats.initialize(*ctx.bumps.get("stats").unwrap());
+    stats.record_burn(true, is_msrm, redeem_amount);
+
+    ctx.accounts
+        .owner_user_account
+        .record_burn(true, is_msrm, redeem_amount, gsrm_amount);
+
+    let checkpoints = &mut ctx.accounts.owner_checkpoints;
+    checkpoints.initialize_untracked(
+        ctx.accounts.owner.key(),
+        *ctx.bumps.get("owner_checkpoints").unwrap(),
+        ctx.accounts.owner.key(),
+    );
+    checkpoints.decrease(gsrm_amount, ctx.accounts.clock.unix_timestamp)?;
+
+    let position_history = &mut ctx.accounts.position_history;
+    position_history.initialize(
+        ctx.accounts.vest_account.key(),
+        *ctx.bumps.get("position_history").unwrap(),
+    );
+    position_history.record(
+        PositionActionKind::Burn,
+        gsrm_amount,
+        ctx.accounts.redeem_ticket.key(),
+        ctx.accounts.clock.unix_timestamp,
+    )?;
+
+    emit!(BurnVestEvent {
+        owner: ctx.accounts.owner.key(),
+        vest_account: ctx.accounts.vest_account.key(),
+        redeem_ticket: ctx.accounts.redeem_ticket.key(),
+        redeem_index: ctx.accounts.redeem_ticket.redeem_index,
+        is_msrm: ctx.accounts.vest_account.is_msrm,
+        gsrm_amount,
+        redeem_amount: ctx.accounts.redeem_ticket.amount,
+        gsrm_burned: ctx.accounts.vest_account.gsrm_burned,
+        timestamp: ctx.accounts.clock.unix_timestamp,
+    });
 
     Ok(())
 }
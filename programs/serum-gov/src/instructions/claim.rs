@@ -2,7 +2,8 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount};
 
 use crate::errors::*;
-use crate::state::ClaimTicket;
+use crate::events::ClaimEvent;
+use crate::state::{Checkpoints, ClaimTicket, PositionActionKind, PositionHistory, Stats, User};
 
 #[derive(Accounts)]
 pub struct Claim<'info> {
@@ -14,12 +15,53 @@ pub struct Claim<'info> {
         seeds = [b"claim_ticket", &claim_ticket.deposit_account.to_bytes()[..]],
         bump,
         constraint = claim_ticket.owner.key() == owner.key() @ SerumGovError::InvalidTicketOwner,
-        constraint = clock.unix_timestamp >= (claim_ticket.created_at + claim_ticket.claim_delay) @ SerumGovError::TicketNotClaimable,
+        constraint = clock.unix_timestamp >= claim_ticket.created_at.checked_add(claim_ticket.claim_delay).ok_or(SerumGovError::MathOverflow)? @ SerumGovError::TicketNotClaimable,
         constraint = claim_ticket.gsrm_amount > 0 @ SerumGovError::TicketNotClaimable,
-        close = owner
+        close = rent_payer
     )]
     pub claim_ticket: Account<'info, ClaimTicket>,
 
+    /// CHECK: Paid the rent for the ClaimTicket, and gets it back.
+    #[account(
+        mut,
+        address = claim_ticket.refund_to() @ SerumGovError::InvalidRentPayer
+    )]
+    pub rent_payer: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [b"position_history", &claim_ticket.deposit_account.to_bytes()[..]],
+        bump,
+        space = PositionHistory::LEN
+    )]
+    pub position_history: Box<Account<'info, PositionHistory>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [b"stats"],
+        bump,
+        space = Stats::LEN
+    )]
+    pub stats: Box<Account<'info, Stats>>,
+
+    #[account(
+        mut,
+        seeds = [b"user", &owner.key().to_bytes()[..]],
+        bump,
+    )]
+    pub owner_user_account: Box<Account<'info, User>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [b"checkpoints", &owner.key().to_bytes()[..]],
+        bump,
+        space = Checkpoints::LEN
+    )]
+    pub owner_checkpoints: Box<Account<'info, Checkpoints>>,
+
     /// CHECK: Just a PDA for vault authorities.
     #[account(
         seeds = [b"authority"],
@@ -70,7 +112,44 @@ pub fn handler(ctx: Context<Claim>) -> Result<()> {
     )?;
 
     let claim_ticket = &mut ctx.accounts.claim_ticket;
+    let gsrm_amount = claim_ticket.gsrm_amount;
     claim_ticket.gsrm_amount = 0;
 
+    let stats = &mut ctx.accounts.stats;
+    stats.initialize(*ctx.bumps.get("stats").unwrap());
+    stats.record_claim(gsrm_amount);
+
+    ctx.accounts.owner_user_account.record_claim(gsrm_amount);
+
+    // Claiming doesn't change the unburned gSRM, but is recorded as a checkpoint anyway.
+    let checkpoints = &mut ctx.accounts.owner_checkpoints;
+    checkpoints.initialize_untracked(
+        ctx.accounts.owner.key(),
+        *ctx.bumps.get("owner_checkpoints").unwrap(),
+        ctx.accounts.owner.key(),
+    );
+    let unburned_gsrm = checkpoints.unburned_gsrm;
+    checkpoints.record(unburned_gsrm, ctx.accounts.clock.unix_timestamp)?;
+
+    let position_history = &mut ctx.accounts.position_history;
+    position_history.initialize(
+        ctx.accounts.claim_ticket.deposit_account,
+        *ctx.bumps.get("position_history").unwrap(),
+    );
+    position_history.record(
+        PositionActionKind::Claim,
+        gsrm_amount,
+        ctx.accounts.claim_ticket.key(),
+        ctx.accounts.clock.unix_timestamp,
+    )?;
+
+    emit!(ClaimEvent {
+        owner: ctx.accounts.owner.key(),
+        claim_ticket: ctx.accounts.claim_ticket.key(),
+        deposit_account: ctx.accounts.claim_ticket.deposit_account,
+        gsrm_amount,
+        timestamp: ctx.accounts.clock.unix_timestamp,
+    });
+
     Ok(())
 }
@@ -0,0 +1,136 @@
+use anchor_lang::{prelude::*, AccountsClose, AccountsExit};
+
+use crate::errors::SerumGovError;
+use crate::events::ClosePositionEvent;
+use crate::state::{LockedAccount, PositionHistory, PositionStatus, User, VestAccount};
+use crate::voter_weight::Position;
+
+/// Closes a fully burned LockedAccount or VestAccount, along with its PositionHistory, and returns
+/// the rent to whoever paid it.
+/// NOTE: The position's ClaimTicket, followed by its RedeemTickets in `redeem_index` order,
+/// must be passed in as `remaining_accounts` to show they're all settled.
+#[derive(Accounts)]
+pub struct ClosePosition<'info> {
+    /// CHECK: LockedAccount or VestAccount, type is checked in the handler.
+    #[account(
+        mut,
+        owner = crate::ID,
+    )]
+    pub position: AccountInfo<'info>,
+
+    /// CHECK: Receives the rent, checked against the position's rent payer in the handler.
+    #[account(mut)]
+    pub rent_payer: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"position_history", &position.key().to_bytes()[..]],
+        bump = position_history.bump,
+        close = rent_payer
+    )]
+    pub position_history: Box<Account<'info, PositionHistory>>,
+
+    /// NOTE: Must belong to the position's owner, checked in the handler.
+    #[account(mut)]
+    pub owner_user_account: Account<'info, User>,
+}
+
+pub fn handler(ctx: Context<ClosePosition>) -> Result<()> {
+    let position = Position::load(&ctx.accounts.position)?;
+
+    if position.refund_to() != ctx.accounts.rent_payer.key() {
+        return err!(SerumGovError::InvalidRentPayer);
+    }
+
+    if position.owner() != ctx.accounts.owner_user_account.owner {
+        return err!(SerumGovError::InvalidPositionOwner);
+    }
+
+    if position.status() != PositionStatus::FullyBurned {
+        return err!(SerumGovError::PositionNotFullyBurned);
+    }
+
+    check_tickets_settled(
+        &ctx.accounts.position.key(),
+        position.redeem_index(),
+        ctx.remaining_accounts,
+    )?;
+
+    let rent_payer = ctx.accounts.rent_payer.to_account_info();
+
+    let (is_msrm, total_gsrm_amount) = match position {
+        Position::Locked(_) => {
+            let mut locked_account: Account<LockedAccount> =
+                Account::try_from(&ctx.accounts.position)?;
+            locked_account.status = PositionStatus::Closed;
+            locked_account.exit(&crate::ID)?;
+            locked_account.close(rent_payer)?;
+            (locked_account.is_msrm, locked_account.total_gsrm_amount)
+        }
+        Position::Vest(_) => {
+            let mut vest_account: Account<VestAccount> = Account::try_from(&ctx.accounts.position)?;
+            vest_account.status = PositionStatus::Closed;
+            vest_account.exit(&crate::ID)?;
+            vest_account.close(rent_payer)?;
+            (vest_account.is_msrm, vest_account.total_gsrm_amount)
+        }
+    };
+
+    let user_account = &mut ctx.accounts.owner_user_account;
+    user_account.closed_positions = user_account
+        .closed_positions
+        .checked_add(1)
+        .ok_or(SerumGovError::MathOverflow)?;
+
+    emit!(ClosePositionEvent {
+        owner: position.owner(),
+        position: ctx.accounts.position.key(),
+        rent_payer: ctx.accounts.rent_payer.key(),
+        is_msrm,
+        total_gsrm_amount,
+    });
+
+    Ok(())
+}
+
+/// CHECK: ClaimTicket and every RedeemTicket issued for `position` must have been closed.
+pub fn check_tickets_settled(
+    position: &Pubkey,
+    redeem_index: u64,
+    tickets: &[AccountInfo],
+) -> Result<()> {
+    if tickets.len() as u64
+        != redeem_index
+            .checked_add(1)
+            .ok_or(SerumGovError::MathOverflow)?
+    {
+        return err!(SerumGovError::UnsettledTickets);
+    }
+
+    for (i, ticket) in tickets.iter().enumerate() {
+        let (expected, _) = if i == 0 {
+            Pubkey::find_program_address(&[b"claim_ticket", &position.to_bytes()[..]], &crate::ID)
+        } else {
+            let index = (i as u64) - 1;
+            Pubkey::find_program_address(
+                &[
+                    b"redeem_ticket",
+                    &position.to_bytes()[..],
+                    index.to_le_bytes().as_ref(),
+                ],
+                &crate::ID,
+            )
+        };
+
+        if ticket.key() != expected {
+            return err!(SerumGovError::UnsettledTickets);
+        }
+
+        // Claiming and redeeming close the tickets.
+        if ticket.owner == &crate::ID && !ticket.data_is_empty() {
+            return err!(SerumGovError::UnsettledTickets);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use anchor_lang::{prelude::*, AccountsClose};
+
+use crate::errors::SerumGovError;
+use crate::events::CloseUserEvent;
+use crate::state::{Checkpoints, User, UserTombstone};
+
+/// Closes the User account of `owner` once all of their positions are closed, along with their
+/// Checkpoints, whose rent goes back to whoever paid it.
+/// NOTE: Its indexes are kept in a UserTombstone, which `init_user` picks up if it's re-created.
+/// NOTE: Migrated Users can have positions closed before `closed_positions` was tracked. They pass in
+/// the LockedAccount address for every `lock_index`, followed by the VestAccount address for every
+/// `vest_index`, as `remaining_accounts` instead.
+#[derive(Accounts)]
+pub struct CloseUser<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user", &owner.key().to_bytes()[..]],
+        bump,
+        close = owner
+    )]
+    pub owner_user_account: Account<'info, User>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [b"user_tombstone", &owner.key().to_bytes()[..]],
+        bump,
+        space = UserTombstone::LEN
+    )]
+    pub owner_tombstone: Account<'info, UserTombstone>,
+
+    /// CHECK: Checkpoints of the owner, which only exists if they deposited since it was added.
+    #[account(
+        mut,
+        seeds = [b"checkpoints", &owner.key().to_bytes()[..]],
+        bump,
+    )]
+    pub owner_checkpoints: AccountInfo<'info>,
+
+    /// CHECK: Receives the rent of the Checkpoints, checked against its rent payer in the handler.
+    #[account(mut)]
+    pub checkpoints_rent_payer: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CloseUser>) -> Result<()> {
+    let user_account = &ctx.accounts.owner_user_account;
+
+    // CHECK: Closing a position needs all of its tickets to be settled first.
+    let positions = user_account
+        .lock_index
+        .checked_add(user_account.vest_index)
+        .ok_or(SerumGovError::MathOverflow)?;
+    if user_account.closed_positions != positions {
+        check_positions_closed(user_account, positions, ctx.remaining_accounts)?;
+    }
+
+    if user_account.open_claim_tickets > 0 || user_account.open_redeem_tickets > 0 {
+        return err!(SerumGovError::UnsettledTickets);
+    }
+
+    msg!(
+        "Closing user: {:?}",
+        (user_account.lock_index, user_account.vest_index)
+    );
+
+    let checkpoints = &ctx.accounts.owner_checkpoints;
+    if checkpoints.owner == &crate::ID && !checkpoints.data_is_empty() {
+        let checkpoints: Account<Checkpoints> = Account::try_from(checkpoints)?;
+        if checkpoints.rent_payer != ctx.accounts.checkpoints_rent_payer.key() {
+            return err!(SerumGovError::InvalidRentPayer);
+        }
+        checkpoints.close(ctx.accounts.checkpoints_rent_payer.clone())?;
+    }
+
+    let tombstone = &mut ctx.accounts.owner_tombstone;
+    tombstone.owner = user_account.owner;
+    tombstone.bump = *ctx.bumps.get("owner_tombstone").unwrap();
+    tombstone.lock_index = user_account.lock_index;
+    tombstone.vest_index = user_account.vest_index;
+
+    emit!(CloseUserEvent {
+        owner: user_account.owner,
+        user_account: user_account.key(),
+        lock_index: user_account.lock_index,
+        vest_index: user_account.vest_index,
+    });
+
+    Ok(())
+}
+
+/// CHECK: Every position of the user is gone, going by the addresses of all of their indexes.
+fn check_positions_closed(
+    user_account: &User,
+    positions: u64,
+    position_accounts: &[AccountInfo],
+) -> Result<()> {
+    if position_accounts.len() as u64 != positions {
+        return err!(SerumGovError::OpenPositions);
+    }
+
+    let indexes = (0..user_account.lock_index)
+        .map(|index| (&b"locked_account"[..], index))
+        .chain((0..user_account.vest_index).map(|index| (&b"vest_account"[..], index)));
+
+    for ((prefix, index), position) in indexes.zip(position_accounts) {
+        let (address, _) = Pubkey::find_program_address(
+            &[
+                prefix,
+                &user_account.owner.to_bytes()[..],
+                index.to_le_bytes().as_ref(),
+            ],
+            &crate::ID,
+        );
+        if position.key() != address {
+            return err!(SerumGovError::InvalidPositionAccount);
+        }
+
+        if position.owner == &crate::ID && !position.data_is_empty() {
+            return err!(SerumGovError::OpenPositions);
+        }
+    }
+
+    Ok(())
+}
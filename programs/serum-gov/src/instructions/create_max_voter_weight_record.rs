@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::events::MaxVoterWeightRecordEvent;
+use crate::state::MaxVoterWeightRecord;
+
+#[derive(Accounts)]
+#[instruction(realm: Pubkey, governing_token_mint: Pubkey)]
+pub struct CreateMaxVoterWeightRecord<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"max_voter_weight_record", &realm.to_bytes()[..], &governing_token_mint.to_bytes()[..]],
+        bump,
+        space = MaxVoterWeightRecord::LEN
+    )]
+    pub max_voter_weight_record: Account<'info, MaxVoterWeightRecord>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<CreateMaxVoterWeightRecord>,
+    realm: Pubkey,
+    governing_token_mint: Pubkey,
+) -> Result<()> {
+    let max_voter_weight_record = &mut ctx.accounts.max_voter_weight_record;
+
+    max_voter_weight_record.realm = realm;
+    max_voter_weight_record.governing_token_mint = governing_token_mint;
+    max_voter_weight_record.max_voter_weight = 0;
+    // NOTE: Expired from the start, `update_max_voter_weight_record` must be called before use.
+    max_voter_weight_record.max_voter_weight_expiry = Some(0);
+
+    emit!(MaxVoterWeightRecordEvent {
+        max_voter_weight_record: max_voter_weight_record.key(),
+        realm,
+        governing_token_mint,
+        max_voter_weight: 0,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::events::VoterWeightRecordEvent;
+use crate::state::VoterWeightRecord;
+
+#[derive(Accounts)]
+#[instruction(realm: Pubkey, governing_token_mint: Pubkey)]
+pub struct CreateVoterWeightRecord<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Owner account for which the record is being created.
+    pub owner: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"voter_weight_record", &realm.to_bytes()[..], &governing_token_mint.to_bytes()[..], &owner.key().to_bytes()[..]],
+        bump,
+        space = VoterWeightRecord::LEN
+    )]
+    pub voter_weight_record: Account<'info, VoterWeightRecord>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<CreateVoterWeightRecord>,
+    realm: Pubkey,
+    governing_token_mint: Pubkey,
+) -> Result<()> {
+    let voter_weight_record = &mut ctx.accounts.voter_weight_record;
+
+    voter_weight_record.realm = realm;
+    voter_weight_record.governing_token_mint = governing_token_mint;
+    voter_weight_record.governing_token_owner = ctx.accounts.owner.key();
+    voter_weight_record.voter_weight = 0;
+    // NOTE: Expired from the start, `update_voter_weight_record` must be called before use.
+    voter_weight_record.voter_weight_expiry = Some(0);
+    voter_weight_record.weight_action = None;
+    voter_weight_record.weight_action_target = None;
+
+    emit!(VoterWeightRecordEvent {
+        voter_weight_record: voter_weight_record.key(),
+        realm,
+        governing_token_mint,
+        governing_token_owner: voter_weight_record.governing_token_owner,
+        voter_weight: 0,
+    });
+
+    Ok(())
+}
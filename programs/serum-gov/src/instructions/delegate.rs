@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SerumGovError;
+use crate::events::DelegateEvent;
+use crate::governance::check_no_active_votes;
+use crate::state::{Config, User};
+
+/// NOTE: TokenOwnerRecords of the owner and of the current delegate, if any, must be passed in
+/// as `remaining_accounts` if checked in Config.
+#[derive(Accounts)]
+pub struct Delegate<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"user", &owner.key().to_bytes()[..]],
+        bump,
+    )]
+    pub owner_user_account: Account<'info, User>,
+}
+
+pub fn handler(ctx: Context<Delegate>, delegate: Pubkey) -> Result<()> {
+    // CHECK: Undelegating has its own instruction, and owners can't delegate to themselves.
+    if delegate == Pubkey::default() || delegate == ctx.accounts.owner.key() {
+        return err!(SerumGovError::InvalidDelegate);
+    }
+
+    // CHECK: Positions can't be moved to another voter while a vote cast with them is live,
+    // or they could vote again on the same proposal.
+    check_no_active_votes(
+        &ctx.accounts.config,
+        &ctx.accounts.owner_user_account.voters(),
+        ctx.remaining_accounts,
+    )?;
+
+    msg!("Delegated voting power to: {:?}", delegate);
+
+    let user_account = &mut ctx.accounts.owner_user_account;
+    user_account.delegate = delegate;
+
+    emit!(DelegateEvent {
+        owner: ctx.accounts.owner.key(),
+        delegate,
+    });
+
+    Ok(())
+}
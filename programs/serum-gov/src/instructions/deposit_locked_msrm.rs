@@ -2,7 +2,13 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
 
 use crate::{
-    state::{ClaimTicket, Config, LockedAccount, User},
+    errors::SerumGovError,
+    events::DepositLockedEvent,
+    state::{
+        Checkpoints, ClaimTicket, Config, LockedAccount, PositionActionKind, PositionHistory,
+        PositionStatus, Stats, User,
+    },
+    voter_weight::lock_terms,
     MSRM_MULTIPLIER,
 };
 
@@ -20,6 +26,15 @@ pub struct DepositLockedMSRM<'info> {
     )]
     pub config: Box<Account<'info, Config>>,
 
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"stats"],
+        bump,
+        space = Stats::LEN
+    )]
+    pub stats: Box<Account<'info, Stats>>,
+
     #[account(
         mut,
         seeds = [b"user", &owner.key().to_bytes()[..]],
@@ -27,6 +42,15 @@ pub struct DepositLockedMSRM<'info> {
     )]
     pub owner_user_account: Account<'info, User>,
 
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"checkpoints", &owner.key().to_bytes()[..]],
+        bump,
+        space = Checkpoints::LEN
+    )]
+    pub owner_checkpoints: Box<Account<'info, Checkpoints>>,
+
     #[account(
         address = config.msrm_mint
     )]
@@ -73,6 +97,15 @@ pub struct DepositLockedMSRM<'info> {
     )]
     pub claim_ticket: Account<'info, ClaimTicket>,
 
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"position_history", &locked_account.key().to_bytes()[..]],
+        bump,
+        space = PositionHistory::LEN
+    )]
+    pub position_history: Box<Account<'info, PositionHistory>>,
+
     pub clock: Sysvar<'info, Clock>,
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
@@ -90,16 +123,30 @@ impl<'info> DepositLockedMSRM<'info> {
     }
 }
 
-pub fn handler(ctx: Context<DepositLockedMSRM>, amount: u64) -> Result<()> {
+pub fn handler(
+    ctx: Context<DepositLockedMSRM>,
+    amount: u64,
+    lockup_end: i64,
+    lock_tier: u8,
+) -> Result<()> {
     if amount <= 0 {
         return Err(ProgramError::InvalidInstructionData.into());
     }
 
+    let (lockup_end, lock_multiplier) = lock_terms(
+        &ctx.accounts.config,
+        lock_tier,
+        lockup_end,
+        ctx.accounts.clock.unix_timestamp,
+    )?;
+
     token::transfer(ctx.accounts.into_deposit_msrm_context(), amount)?;
 
     let user_account = &mut ctx.accounts.owner_user_account;
 
-    let gsrm_amount = amount.checked_mul(MSRM_MULTIPLIER).unwrap();
+    let gsrm_amount = amount
+        .checked_mul(MSRM_MULTIPLIER)
+        .ok_or(SerumGovError::MathOverflow)?;
 
     let locked_account = &mut ctx.accounts.locked_account;
     locked_account.owner = ctx.accounts.owner.key();
@@ -110,6 +157,11 @@ pub fn handler(ctx: Context<DepositLockedMSRM>, amount: u64) -> Result<()> {
     locked_account.created_at = ctx.accounts.clock.unix_timestamp;
     locked_account.total_gsrm_amount = gsrm_amount;
     locked_account.gsrm_burned = 0;
+    locked_account.lockup_end = lockup_end;
+    locked_account.status = PositionStatus::Active;
+    locked_account.rent_payer = ctx.accounts.payer.key();
+    locked_account.lock_tier = lock_tier;
+    locked_account.lock_multiplier = lock_multiplier;
 
     let claim_ticket = &mut ctx.accounts.claim_ticket;
     claim_ticket.owner = ctx.accounts.owner.key();
@@ -118,8 +170,53 @@ pub fn handler(ctx: Context<DepositLockedMSRM>, amount: u64) -> Result<()> {
     claim_ticket.created_at = ctx.accounts.clock.unix_timestamp;
     claim_ticket.claim_delay = ctx.accounts.config.claim_delay;
     claim_ticket.gsrm_amount = gsrm_amount;
-
-    user_account.lock_index = user_account.lock_index.checked_add(1).unwrap();
+    claim_ticket.rent_payer = ctx.accounts.payer.key();
+
+    let checkpoints = &mut ctx.accounts.owner_checkpoints;
+    // Positions opened before the account was created aren't accounted for in it.
+    let untracked_positions = user_account.lock_index > 0 || user_account.vest_index > 0;
+    checkpoints.initialize(
+        ctx.accounts.owner.key(),
+        *ctx.bumps.get("owner_checkpoints").unwrap(),
+        untracked_positions,
+        ctx.accounts.payer.key(),
+    );
+    checkpoints.increase(gsrm_amount, ctx.accounts.clock.unix_timestamp)?;
+
+    user_account.lock_index = user_account
+        .lock_index
+        .checked_add(1)
+        .ok_or(SerumGovError::MathOverflow)?;
+    user_account.record_deposit(false, true, amount, gsrm_amount);
+
+    let stats = &mut ctx.accounts.stats;
+    stats.initialize(*ctx.bumps.get("stats").unwrap());
+    stats.record_deposit(false, true, amount, gsrm_amount)?;
+
+    let position_history = &mut ctx.accounts.position_history;
+    position_history.initialize(
+        ctx.accounts.locked_account.key(),
+        *ctx.bumps.get("position_history").unwrap(),
+    );
+    position_history.record(
+        PositionActionKind::Deposit,
+        ctx.accounts.locked_account.total_gsrm_amount,
+        ctx.accounts.claim_ticket.key(),
+        ctx.accounts.clock.unix_timestamp,
+    )?;
+
+    emit!(DepositLockedEvent {
+        owner: ctx.accounts.owner.key(),
+        locked_account: ctx.accounts.locked_account.key(),
+        claim_ticket: ctx.accounts.claim_ticket.key(),
+        lock_index: ctx.accounts.locked_account.lock_index,
+        is_msrm: ctx.accounts.locked_account.is_msrm,
+        amount,
+        gsrm_amount: ctx.accounts.locked_account.total_gsrm_amount,
+        lockup_end,
+        lock_tier,
+        timestamp: ctx.accounts.clock.unix_timestamp,
+    });
 
     Ok(())
 }
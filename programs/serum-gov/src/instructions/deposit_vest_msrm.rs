@@ -2,7 +2,12 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
 
 use crate::{
-    state::{ClaimTicket, Config, User, VestAccount},
+    errors::SerumGovError,
+    events::DepositVestEvent,
+    state::{
+        Checkpoints, ClaimTicket, Config, PositionActionKind, PositionHistory, PositionStatus,
+        Stats, User, VestAccount,
+    },
     MSRM_MULTIPLIER,
 };
 
@@ -20,6 +25,15 @@ pub struct DepositVestMSRM<'info> {
     )]
     pub config: Box<Account<'info, Config>>,
 
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"stats"],
+        bump,
+        space = Stats::LEN
+    )]
+    pub stats: Box<Account<'info, Stats>>,
+
     #[account(
         mut,
         seeds = [b"user", &owner.key().to_bytes()[..]],
@@ -27,6 +41,15 @@ pub struct DepositVestMSRM<'info> {
     )]
     pub owner_user_account: Account<'info, User>,
 
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"checkpoints", &owner.key().to_bytes()[..]],
+        bump,
+        space = Checkpoints::LEN
+    )]
+    pub owner_checkpoints: Box<Account<'info, Checkpoints>>,
+
     #[account(
         init,
         payer = payer,
@@ -45,6 +68,15 @@ pub struct DepositVestMSRM<'info> {
     )]
     pub claim_ticket: Account<'info, ClaimTicket>,
 
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"position_history", &vest_account.key().to_bytes()[..]],
+        bump,
+        space = PositionHistory::LEN
+    )]
+    pub position_history: Box<Account<'info, PositionHistory>>,
+
     #[account(address = config.msrm_mint)]
     pub msrm_mint: Account<'info, Mint>,
 
@@ -98,7 +130,9 @@ pub fn handler(ctx: Context<DepositVestMSRM>, amount: u64) -> Result<()> {
     let config = &ctx.accounts.config;
     let user_account = &mut ctx.accounts.owner_user_account;
 
-    let gsrm_amount = amount.checked_mul(MSRM_MULTIPLIER).unwrap();
+    let gsrm_amount = amount
+        .checked_mul(MSRM_MULTIPLIER)
+        .ok_or(SerumGovError::MathOverflow)?;
 
     let vest_account = &mut ctx.accounts.vest_account;
     vest_account.bump = *ctx.bumps.get("vest_account").unwrap();
@@ -111,6 +145,8 @@ pub fn handler(ctx: Context<DepositVestMSRM>, amount: u64) -> Result<()> {
     vest_account.linear_vesting_period = config.linear_vesting_period;
     vest_account.total_gsrm_amount = gsrm_amount;
     vest_account.gsrm_burned = 0;
+    vest_account.status = PositionStatus::Active;
+    vest_account.rent_payer = ctx.accounts.payer.key();
 
     let claim_ticket = &mut ctx.accounts.claim_ticket;
     claim_ticket.owner = ctx.accounts.owner.key();
@@ -119,8 +155,53 @@ pub fn handler(ctx: Context<DepositVestMSRM>, amount: u64) -> Result<()> {
     claim_ticket.created_at = ctx.accounts.clock.unix_timestamp;
     claim_ticket.claim_delay = config.claim_delay;
     claim_ticket.gsrm_amount = gsrm_amount;
-
-    user_account.vest_index = user_account.vest_index.checked_add(1).unwrap();
+    claim_ticket.rent_payer = ctx.accounts.payer.key();
+
+    let checkpoints = &mut ctx.accounts.owner_checkpoints;
+    // Positions opened before the account was created aren't accounted for in it.
+    let untracked_positions = user_account.lock_index > 0 || user_account.vest_index > 0;
+    checkpoints.initialize(
+        ctx.accounts.owner.key(),
+        *ctx.bumps.get("owner_checkpoints").unwrap(),
+        untracked_positions,
+        ctx.accounts.payer.key(),
+    );
+    checkpoints.increase(gsrm_amount, ctx.accounts.clock.unix_timestamp)?;
+
+    user_account.vest_index = user_account
+        .vest_index
+        .checked_add(1)
+        .ok_or(SerumGovError::MathOverflow)?;
+    user_account.record_deposit(true, true, amount, gsrm_amount);
+
+    let stats = &mut ctx.accounts.stats;
+    stats.initialize(*ctx.bumps.get("stats").unwrap());
+    stats.record_deposit(true, true, amount, gsrm_amount)?;
+
+    let position_history = &mut ctx.accounts.position_history;
+    position_history.initialize(
+        ctx.accounts.vest_account.key(),
+        *ctx.bumps.get("position_history").unwrap(),
+    );
+    position_history.record(
+        PositionActionKind::Deposit,
+        ctx.accounts.vest_account.total_gsrm_amount,
+        ctx.accounts.claim_ticket.key(),
+        ctx.accounts.clock.unix_timestamp,
+    )?;
+
+    emit!(DepositVestEvent {
+        owner: ctx.accounts.owner.key(),
+        vest_account: ctx.accounts.vest_account.key(),
+        claim_ticket: ctx.accounts.claim_ticket.key(),
+        vest_index: ctx.accounts.vest_account.vest_index,
+        is_msrm: ctx.accounts.vest_account.is_msrm,
+        amount,
+        gsrm_amount: ctx.accounts.vest_account.total_gsrm_amount,
+        cliff_period: ctx.accounts.vest_account.cliff_period,
+        linear_vesting_period: ctx.accounts.vest_account.linear_vesting_period,
+        timestamp: ctx.accounts.clock.unix_timestamp,
+    });
 
     Ok(())
 }
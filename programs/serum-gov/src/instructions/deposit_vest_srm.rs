@@ -1,7 +1,12 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
 
-use crate::state::{ClaimTicket, Config, User, VestAccount};
+use crate::errors::SerumGovError;
+use crate::events::DepositVestEvent;
+use crate::state::{
+    Checkpoints, ClaimTicket, Config, PositionActionKind, PositionHistory, PositionStatus, Stats,
+    User, VestAccount,
+};
 
 #[derive(Accounts)]
 pub struct DepositVestSRM<'info> {
@@ -17,6 +22,15 @@ pub struct DepositVestSRM<'info> {
     )]
     pub config: Box<Account<'info, Config>>,
 
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"stats"],
+        bump,
+        space = Stats::LEN
+    )]
+    pub stats: Box<Account<'info, Stats>>,
+
     #[account(
         mut,
         seeds = [b"user", &owner.key().to_bytes()[..]],
@@ -24,6 +38,15 @@ pub struct DepositVestSRM<'info> {
     )]
     pub owner_user_account: Account<'info, User>,
 
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"checkpoints", &owner.key().to_bytes()[..]],
+        bump,
+        space = Checkpoints::LEN
+    )]
+    pub owner_checkpoints: Box<Account<'info, Checkpoints>>,
+
     #[account(
         init,
         payer = payer,
@@ -42,6 +65,15 @@ pub struct DepositVestSRM<'info> {
     )]
     pub claim_ticket: Account<'info, ClaimTicket>,
 
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"position_history", &vest_account.key().to_bytes()[..]],
+        bump,
+        space = PositionHistory::LEN
+    )]
+    pub position_history: Box<Account<'info, PositionHistory>>,
+
     #[account(address = config.srm_mint)]
     pub srm_mint: Account<'info, Mint>,
 
@@ -106,6 +138,8 @@ pub fn handler(ctx: Context<DepositVestSRM>, amount: u64) -> Result<()> {
     vest_account.linear_vesting_period = config.linear_vesting_period;
     vest_account.total_gsrm_amount = amount;
     vest_account.gsrm_burned = 0;
+    vest_account.status = PositionStatus::Active;
+    vest_account.rent_payer = ctx.accounts.payer.key();
 
     let claim_ticket = &mut ctx.accounts.claim_ticket;
     claim_ticket.owner = ctx.accounts.owner.key();
@@ -114,8 +148,53 @@ pub fn handler(ctx: Context<DepositVestSRM>, amount: u64) -> Result<()> {
     claim_ticket.created_at = ctx.accounts.clock.unix_timestamp;
     claim_ticket.claim_delay = config.claim_delay;
     claim_ticket.gsrm_amount = amount;
-
-    user_account.vest_index = user_account.vest_index.checked_add(1).unwrap();
+    claim_ticket.rent_payer = ctx.accounts.payer.key();
+
+    let checkpoints = &mut ctx.accounts.owner_checkpoints;
+    // Positions opened before the account was created aren't accounted for in it.
+    let untracked_positions = user_account.lock_index > 0 || user_account.vest_index > 0;
+    checkpoints.initialize(
+        ctx.accounts.owner.key(),
+        *ctx.bumps.get("owner_checkpoints").unwrap(),
+        untracked_positions,
+        ctx.accounts.payer.key(),
+    );
+    checkpoints.increase(amount, ctx.accounts.clock.unix_timestamp)?;
+
+    user_account.vest_index = user_account
+        .vest_index
+        .checked_add(1)
+        .ok_or(SerumGovError::MathOverflow)?;
+    user_account.record_deposit(true, false, amount, amount);
+
+    let stats = &mut ctx.accounts.stats;
+    stats.initialize(*ctx.bumps.get("stats").unwrap());
+    stats.record_deposit(true, false, amount, amount)?;
+
+    let position_history = &mut ctx.accounts.position_history;
+    position_history.initialize(
+        ctx.accounts.vest_account.key(),
+        *ctx.bumps.get("position_history").unwrap(),
+    );
+    position_history.record(
+        PositionActionKind::Deposit,
+        ctx.accounts.vest_account.total_gsrm_amount,
+        ctx.accounts.claim_ticket.key(),
+        ctx.accounts.clock.unix_timestamp,
+    )?;
+
+    emit!(DepositVestEvent {
+        owner: ctx.accounts.owner.key(),
+        vest_account: ctx.accounts.vest_account.key(),
+        claim_ticket: ctx.accounts.claim_ticket.key(),
+        vest_index: ctx.accounts.vest_account.vest_index,
+        is_msrm: ctx.accounts.vest_account.is_msrm,
+        amount,
+        gsrm_amount: ctx.accounts.vest_account.total_gsrm_amount,
+        cliff_period: ctx.accounts.vest_account.cliff_period,
+        linear_vesting_period: ctx.accounts.vest_account.linear_vesting_period,
+        timestamp: ctx.accounts.clock.unix_timestamp,
+    });
 
     Ok(())
 }
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SerumGovError;
+use crate::events::ExtendLockEvent;
+use crate::state::{Config, LockedAccount, PositionStatus};
+use crate::voter_weight::{max_lock_duration, Position};
+
+/// Moves the lockup end of a LockedAccount further out.
+#[derive(Accounts)]
+pub struct ExtendLock<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [b"locked_account", &owner.key().to_bytes()[..], locked_account.lock_index.to_le_bytes().as_ref()],
+        bump,
+        constraint = locked_account.status == PositionStatus::Active @ SerumGovError::PositionNotActive,
+    )]
+    pub locked_account: Box<Account<'info, LockedAccount>>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<ExtendLock>, lockup_end: i64) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let now = ctx.accounts.clock.unix_timestamp;
+    let old_lockup_end = ctx.accounts.locked_account.lockup_end;
+
+    // CHECK: Lockups can only be extended, up to the max lock duration from now.
+    if lockup_end <= old_lockup_end
+        || lockup_end <= now
+        || lockup_end - now > max_lock_duration(config, &ctx.accounts.locked_account)
+    {
+        return err!(SerumGovError::InvalidLockupEnd);
+    }
+
+    msg!("Extended lockup: {:?}", (old_lockup_end, lockup_end));
+
+    let locked_account = &mut ctx.accounts.locked_account;
+    locked_account.lockup_end = lockup_end;
+
+    // Voting power depends on the time left in the lockup.
+    let voting_power =
+        Position::Locked(LockedAccount::clone(locked_account)).voting_power(config, now)?;
+
+    emit!(ExtendLockEvent {
+        owner: ctx.accounts.owner.key(),
+        locked_account: locked_account.key(),
+        lock_index: locked_account.lock_index,
+        old_lockup_end,
+        new_lockup_end: lockup_end,
+        voting_power,
+        timestamp: now,
+    });
+
+    Ok(())
+}
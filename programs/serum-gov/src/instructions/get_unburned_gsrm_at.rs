@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SerumGovError;
+use crate::state::Checkpoints;
+
+/// Returns the unburned gSRM amount of `checkpoints.owner` at `timestamp`, for snapshot based votes.
+/// NOTE: This isn't the voter weight of the owner, see Checkpoints.
+#[derive(Accounts)]
+pub struct GetUnburnedGSRMAt<'info> {
+    #[account(
+        seeds = [b"checkpoints", &checkpoints.owner.to_bytes()[..]],
+        bump = checkpoints.bump,
+    )]
+    pub checkpoints: Account<'info, Checkpoints>,
+}
+
+pub fn handler(ctx: Context<GetUnburnedGSRMAt>, timestamp: i64) -> Result<u64> {
+    let unburned_gsrm = match ctx.accounts.checkpoints.unburned_gsrm_at(timestamp) {
+        Some(unburned_gsrm) => unburned_gsrm,
+        None => return err!(SerumGovError::CheckpointNotFound),
+    };
+    msg!("Unburned gSRM at {}: {}", timestamp, unburned_gsrm);
+
+    Ok(unburned_gsrm)
+}
@@ -0,0 +1,201 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::errors::SerumGovError;
+use crate::events::IncreaseLockedEvent;
+use crate::state::{
+    Checkpoints, ClaimTicket, Config, LockedAccount, PositionActionKind, PositionHistory,
+    PositionStatus, Stats, User,
+};
+use crate::voter_weight::top_up_terms;
+
+/// Adds SRM to an existing LockedAccount, issuing the extra gSRM through its ClaimTicket.
+/// NOTE: The previous ClaimTicket of the LockedAccount must have been claimed.
+/// NOTE: Top-ups restart the min position age of the whole LockedAccount, including the balance
+/// it already had, so only the owner can top up their positions.
+/// NOTE: Positions of a lock tier are locked for the tier's full term again.
+#[derive(Accounts)]
+pub struct IncreaseLockedSRM<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"stats"],
+        bump,
+        space = Stats::LEN
+    )]
+    pub stats: Box<Account<'info, Stats>>,
+
+    #[account(
+        mut,
+        seeds = [b"user", &owner.key().to_bytes()[..]],
+        bump,
+    )]
+    pub owner_user_account: Box<Account<'info, User>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"checkpoints", &owner.key().to_bytes()[..]],
+        bump,
+        space = Checkpoints::LEN
+    )]
+    pub owner_checkpoints: Box<Account<'info, Checkpoints>>,
+
+    #[account(
+        address = config.srm_mint
+    )]
+    pub srm_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = srm_mint,
+        token::authority = payer
+    )]
+    pub payer_srm_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Just a PDA for vault authorities.
+    #[account(
+        seeds = [b"authority"],
+        bump,
+    )]
+    pub authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", &srm_mint.key().to_bytes()[..]],
+        bump,
+        token::mint = srm_mint,
+        token::authority = authority,
+    )]
+    pub srm_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"locked_account", &owner.key().to_bytes()[..], locked_account.lock_index.to_le_bytes().as_ref()],
+        bump,
+        constraint = !locked_account.is_msrm @ SerumGovError::InvalidPositionAccount,
+        constraint = locked_account.status == PositionStatus::Active @ SerumGovError::PositionNotActive,
+    )]
+    pub locked_account: Box<Account<'info, LockedAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"claim_ticket", &locked_account.key().to_bytes()[..]],
+        bump,
+        space = ClaimTicket::LEN
+    )]
+    pub claim_ticket: Account<'info, ClaimTicket>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"position_history", &locked_account.key().to_bytes()[..]],
+        bump,
+        space = PositionHistory::LEN
+    )]
+    pub position_history: Box<Account<'info, PositionHistory>>,
+
+    pub clock: Sysvar<'info, Clock>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> IncreaseLockedSRM<'info> {
+    fn into_deposit_srm_context(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.payer_srm_account.to_account_info().clone(),
+            to: self.srm_vault.to_account_info().clone(),
+            authority: self.payer.to_account_info().clone(),
+        };
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+pub fn handler(ctx: Context<IncreaseLockedSRM>, amount: u64) -> Result<()> {
+    if amount == 0 {
+        return Err(ProgramError::InvalidInstructionData.into());
+    }
+
+    token::transfer(ctx.accounts.into_deposit_srm_context(), amount)?;
+
+    let locked_account = &mut ctx.accounts.locked_account;
+    locked_account.total_gsrm_amount = locked_account
+        .total_gsrm_amount
+        .checked_add(amount)
+        .ok_or(SerumGovError::MathOverflow)?;
+    // Top-ups restart the min position age of the whole position, so they can't be used to swing a vote either.
+    locked_account.created_at = ctx.accounts.clock.unix_timestamp;
+
+    let (lockup_end, lock_multiplier) = top_up_terms(
+        &ctx.accounts.config,
+        locked_account,
+        ctx.accounts.clock.unix_timestamp,
+    )?;
+    locked_account.lockup_end = lockup_end;
+    locked_account.lock_multiplier = lock_multiplier;
+
+    let claim_ticket = &mut ctx.accounts.claim_ticket;
+    claim_ticket.owner = ctx.accounts.owner.key();
+    claim_ticket.deposit_account = locked_account.key();
+    claim_ticket.bump = *ctx.bumps.get("claim_ticket").unwrap();
+    claim_ticket.created_at = ctx.accounts.clock.unix_timestamp;
+    claim_ticket.claim_delay = ctx.accounts.config.claim_delay;
+    claim_ticket.gsrm_amount = amount;
+    claim_ticket.rent_payer = ctx.accounts.payer.key();
+
+    let checkpoints = &mut ctx.accounts.owner_checkpoints;
+    checkpoints.initialize_untracked(
+        ctx.accounts.owner.key(),
+        *ctx.bumps.get("owner_checkpoints").unwrap(),
+        ctx.accounts.payer.key(),
+    );
+    checkpoints.increase(amount, ctx.accounts.clock.unix_timestamp)?;
+
+    ctx.accounts
+        .owner_user_account
+        .record_deposit(false, false, amount, amount);
+
+    let stats = &mut ctx.accounts.stats;
+    stats.initialize(*ctx.bumps.get("stats").unwrap());
+    stats.record_deposit(false, false, amount, amount)?;
+
+    let position_history = &mut ctx.accounts.position_history;
+    position_history.initialize(
+        ctx.accounts.locked_account.key(),
+        *ctx.bumps.get("position_history").unwrap(),
+    );
+    position_history.record(
+        PositionActionKind::Deposit,
+        amount,
+        ctx.accounts.claim_ticket.key(),
+        ctx.accounts.clock.unix_timestamp,
+    )?;
+
+    emit!(IncreaseLockedEvent {
+        owner: ctx.accounts.owner.key(),
+        locked_account: ctx.accounts.locked_account.key(),
+        claim_ticket: ctx.accounts.claim_ticket.key(),
+        lock_index: ctx.accounts.locked_account.lock_index,
+        is_msrm: ctx.accounts.locked_account.is_msrm,
+        amount,
+        gsrm_amount: amount,
+        total_gsrm_amount: ctx.accounts.locked_account.total_gsrm_amount,
+        lockup_end: ctx.accounts.locked_account.lockup_end,
+        timestamp: ctx.accounts.clock.unix_timestamp,
+    });
+
+    Ok(())
+}
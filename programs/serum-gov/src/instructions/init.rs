@@ -5,7 +5,9 @@ use solana_program::program::invoke_signed;
 
 #[cfg(not(feature = "test-bpf"))]
 use crate::config::mints::{MSRM, SRM};
-use crate::state::Config;
+use crate::events::InitEvent;
+use crate::state::{Config, LockTier, Stats, VestWeightMode, WeightCurve, MAX_LOCK_TIERS};
+use crate::BASIS_POINTS;
 
 #[derive(Accounts)]
 pub struct Init<'info> {
@@ -29,6 +31,15 @@ pub struct Init<'info> {
     )]
     pub config: Box<Account<'info, Config>>,
 
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"stats"],
+        bump,
+        space = Stats::LEN
+    )]
+    pub stats: Box<Account<'info, Stats>>,
+
     /// NOTE: Decimals have been kept same as SRM.
     #[account(
         init,
@@ -103,6 +114,13 @@ pub fn handler(
     cliff_period: i64,
     linear_vesting_period: i64,
 ) -> Result<()> {
+    Config::validate_params(
+        claim_delay,
+        redeem_delay,
+        cliff_period,
+        linear_vesting_period,
+    )?;
+
     msg!("Initializing Serum Gov");
     msg!(
         "Config params: {:?}",
@@ -122,6 +140,29 @@ pub fn handler(
     config.redeem_delay = redeem_delay;
     config.cliff_period = cliff_period;
     config.linear_vesting_period = linear_vesting_period;
+    config.max_lockup_period = 0;
+    config.max_lockup_multiplier = BASIS_POINTS;
+    config.vest_weight_mode = VestWeightMode::Full;
+    config.unvested_weight = BASIS_POINTS;
+    config.governance_program = Pubkey::default();
+    config.realm = Pubkey::default();
+    config.governing_token_mint = Pubkey::default();
+    config.min_position_age = 0;
+    config.weight_curve = WeightCurve::Linear;
+    config.max_owner_weight = 0;
+    config.lock_tiers = [LockTier::default(); MAX_LOCK_TIERS];
+    config.max_lock_multiplier = 0;
+
+    let stats = &mut ctx.accounts.stats;
+    stats.bump = *ctx.bumps.get("stats").unwrap();
+    stats.srm_locked = 0;
+    stats.msrm_locked = 0;
+    stats.srm_vesting = 0;
+    stats.msrm_vesting = 0;
+    stats.gsrm_unclaimed = 0;
+    stats.srm_redeemable = 0;
+    stats.msrm_redeemable = 0;
+    stats.underflows = 0;
 
     let ix = create_metadata_accounts_v3(
         meta_id(),
@@ -157,5 +198,16 @@ pub fn handler(
 
     invoke_signed(&ix, &cpi_account_infos, &[&[b"authority", &[auth_bump]]])?;
 
+    emit!(InitEvent {
+        config_authority,
+        gsrm_mint: ctx.accounts.gsrm_mint.key(),
+        srm_mint: ctx.accounts.srm_mint.key(),
+        msrm_mint: ctx.accounts.msrm_mint.key(),
+        claim_delay,
+        redeem_delay,
+        cliff_period,
+        linear_vesting_period,
+    });
+
     Ok(())
 }
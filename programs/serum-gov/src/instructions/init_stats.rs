@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+
+use crate::events::InitStatsEvent;
+use crate::state::{Config, ConfigField, ConfigHistory, ConfigValue, Stats};
+
+/// Creates or re-seeds the Stats account, for deployments initialized before it existed.
+/// NOTE: Totals are seeded by the config authority from the existing positions and tickets,
+/// and can be seeded again to fix drift reported by `audit_vaults`. Since they feed the max voter
+/// weight, every total changed is recorded in ConfigHistory.
+#[derive(Accounts)]
+pub struct InitStats<'info> {
+    #[account(mut)]
+    pub config_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump,
+        has_one = config_authority
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = config_authority,
+        seeds = [b"stats"],
+        bump,
+        space = Stats::LEN
+    )]
+    pub stats: Account<'info, Stats>,
+
+    #[account(
+        init_if_needed,
+        payer = config_authority,
+        seeds = [b"config_history"],
+        bump,
+        space = ConfigHistory::LEN
+    )]
+    pub config_history: Box<Account<'info, ConfigHistory>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handler(
+    ctx: Context<InitStats>,
+    srm_locked: u64,
+    msrm_locked: u64,
+    srm_vesting: u64,
+    msrm_vesting: u64,
+    gsrm_unclaimed: u64,
+    srm_redeemable: u64,
+    msrm_redeemable: u64,
+) -> Result<()> {
+    let stats = &mut ctx.accounts.stats;
+
+    let signer = ctx.accounts.config_authority.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    let config_history = &mut ctx.accounts.config_history;
+    config_history.bump = *ctx.bumps.get("config_history").unwrap();
+    for (field, old_value, new_value) in [
+        (ConfigField::StatsSrmLocked, stats.srm_locked, srm_locked),
+        (ConfigField::StatsMsrmLocked, stats.msrm_locked, msrm_locked),
+        (ConfigField::StatsSrmVesting, stats.srm_vesting, srm_vesting),
+        (
+            ConfigField::StatsMsrmVesting,
+            stats.msrm_vesting,
+            msrm_vesting,
+        ),
+        (
+            ConfigField::StatsGsrmUnclaimed,
+            stats.gsrm_unclaimed,
+            gsrm_unclaimed,
+        ),
+        (
+            ConfigField::StatsSrmRedeemable,
+            stats.srm_redeemable,
+            srm_redeemable,
+        ),
+        (
+            ConfigField::StatsMsrmRedeemable,
+            stats.msrm_redeemable,
+            msrm_redeemable,
+        ),
+        (ConfigField::StatsUnderflows, stats.underflows, 0),
+    ] {
+        config_history.record(
+            field,
+            ConfigValue::U64(old_value),
+            ConfigValue::U64(new_value),
+            signer,
+            timestamp,
+        )?;
+    }
+
+    stats.bump = *ctx.bumps.get("stats").unwrap();
+    stats.srm_locked = srm_locked;
+    stats.msrm_locked = msrm_locked;
+    stats.srm_vesting = srm_vesting;
+    stats.msrm_vesting = msrm_vesting;
+    stats.gsrm_unclaimed = gsrm_unclaimed;
+    stats.srm_redeemable = srm_redeemable;
+    stats.msrm_redeemable = msrm_redeemable;
+    stats.underflows = 0;
+
+    emit!(InitStatsEvent {
+        srm_locked,
+        msrm_locked,
+        srm_vesting,
+        msrm_vesting,
+        gsrm_unclaimed,
+        srm_redeemable,
+        msrm_redeemable,
+    });
+
+    Ok(())
+}
@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
 
-use crate::state::User;
+use crate::errors::SerumGovError;
+use crate::events::InitUserEvent;
+use crate::state::{User, UserTombstone};
 
 #[derive(Accounts)]
 #[instruction(owner: Pubkey)]
@@ -17,6 +19,13 @@ pub struct InitUser<'info> {
     )]
     pub user_account: Account<'info, User>,
 
+    /// CHECK: Only exists if the user was closed before, in which case it's read in the handler.
+    #[account(
+        seeds = [b"user_tombstone", &owner.to_bytes()[..]],
+        bump,
+    )]
+    pub user_tombstone: AccountInfo<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -25,8 +34,35 @@ pub fn handler(ctx: Context<InitUser>, owner: Pubkey) -> Result<()> {
 
     user.owner = owner;
     user.bump = *ctx.bumps.get("user_account").unwrap();
-    user.lock_index = 0;
-    user.vest_index = 0;
+    // Carrying on from the indexes of a closed User, so old position seeds aren't reused.
+    let (lock_index, vest_index) = if ctx.accounts.user_tombstone.owner == &crate::ID {
+        let tombstone: Account<UserTombstone> = Account::try_from(&ctx.accounts.user_tombstone)?;
+        (tombstone.lock_index, tombstone.vest_index)
+    } else {
+        (0, 0)
+    };
+
+    user.lock_index = lock_index;
+    user.vest_index = vest_index;
+    user.delegate = Pubkey::default();
+    user.srm_locked = 0;
+    user.msrm_locked = 0;
+    user.srm_vesting = 0;
+    user.msrm_vesting = 0;
+    user.gsrm_unclaimed = 0;
+    user.srm_redeemable = 0;
+    user.msrm_redeemable = 0;
+    user.gsrm_burned = 0;
+    user.open_claim_tickets = 0;
+    user.open_redeem_tickets = 0;
+    user.closed_positions = lock_index
+        .checked_add(vest_index)
+        .ok_or(SerumGovError::MathOverflow)?;
+
+    emit!(InitUserEvent {
+        owner,
+        user_account: user.key(),
+    });
 
     Ok(())
 }
@@ -0,0 +1,176 @@
+use std::cmp;
+
+use anchor_lang::{prelude::*, AccountsClose, AccountsExit};
+
+use crate::errors::SerumGovError;
+use crate::events::MergeLockedAccountsEvent;
+use crate::instructions::close_position::check_tickets_settled;
+use crate::state::{LockedAccount, PositionActionKind, PositionHistory, PositionStatus, User};
+use crate::voter_weight::Position;
+
+/// Folds other LockedAccounts of the owner into `locked_account`, closing them.
+/// NOTE: Each merged LockedAccount is passed in as `remaining_accounts`, followed by its
+/// ClaimTicket and its RedeemTickets in `redeem_index` order, which must all be settled, then its
+/// rent payer and its PositionHistory, which is closed along with it.
+#[derive(Accounts)]
+pub struct MergeLockedAccounts<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user", &owner.key().to_bytes()[..]],
+        bump,
+    )]
+    pub owner_user_account: Box<Account<'info, User>>,
+
+    #[account(
+        mut,
+        seeds = [b"locked_account", &owner.key().to_bytes()[..], locked_account.lock_index.to_le_bytes().as_ref()],
+        bump,
+        constraint = locked_account.status == PositionStatus::Active @ SerumGovError::PositionNotActive,
+    )]
+    pub locked_account: Box<Account<'info, LockedAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [b"position_history", &locked_account.key().to_bytes()[..]],
+        bump,
+        space = PositionHistory::LEN
+    )]
+    pub position_history: Box<Account<'info, PositionHistory>>,
+
+    pub clock: Sysvar<'info, Clock>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, MergeLockedAccounts<'info>>) -> Result<()> {
+    if ctx.remaining_accounts.is_empty() {
+        return err!(SerumGovError::InvalidPositionAccount);
+    }
+
+    let owner = ctx.accounts.owner.key();
+    let target = ctx.accounts.locked_account.key();
+    let now = ctx.accounts.clock.unix_timestamp;
+
+    let position_history = &mut ctx.accounts.position_history;
+    position_history.initialize(target, *ctx.bumps.get("position_history").unwrap());
+
+    let mut merged: Vec<Pubkey> = Vec::new();
+
+    let mut accounts = ctx.remaining_accounts;
+    while let Some((source_info, rest)) = accounts.split_first() {
+        // CHECK: Same account can't be merged twice, or into itself.
+        if source_info.key() == target || merged.contains(source_info.key) {
+            return err!(SerumGovError::DuplicatePositionAccount);
+        }
+
+        let source = match Position::load(source_info)? {
+            Position::Locked(locked_account) => locked_account,
+            Position::Vest(_) => return err!(SerumGovError::InvalidPositionAccount),
+        };
+
+        if source.owner != owner {
+            return err!(SerumGovError::InvalidPositionOwner);
+        }
+
+        if source.status != PositionStatus::Active {
+            return err!(SerumGovError::PositionNotActive);
+        }
+
+        // CHECK: Lock tiers weigh positions differently, so only positions of the same tier can be merged.
+        let locked_account = &mut ctx.accounts.locked_account;
+        if source.is_msrm != locked_account.is_msrm
+            || source.lock_tier != locked_account.lock_tier
+            || source.lock_multiplier != locked_account.lock_multiplier
+        {
+            return err!(SerumGovError::IncompatiblePositions);
+        }
+
+        // CHECK: Tickets must be settled, since RedeemTickets record into the PositionHistory closed below.
+        let tickets = usize::try_from(source.redeem_index)
+            .ok()
+            .and_then(|redeem_index| redeem_index.checked_add(1))
+            .ok_or(SerumGovError::MathOverflow)?;
+        if rest.len() < tickets {
+            return err!(SerumGovError::UnsettledTickets);
+        }
+        let (tickets, rest) = rest.split_at(tickets);
+        check_tickets_settled(source_info.key, source.redeem_index, tickets)?;
+
+        let (rent_payer, source_history) = match rest {
+            [rent_payer, source_history, ..] => (rent_payer, source_history),
+            _ => return err!(SerumGovError::InvalidPositionAccount),
+        };
+        accounts = &rest[2..];
+
+        if rent_payer.key() != source.refund_to() {
+            return err!(SerumGovError::InvalidRentPayer);
+        }
+
+        let (expected, _) = Pubkey::find_program_address(
+            &[b"position_history", &source_info.key().to_bytes()[..]],
+            &crate::ID,
+        );
+        if source_history.key() != expected {
+            return err!(SerumGovError::InvalidPositionAccount);
+        }
+
+        locked_account.total_gsrm_amount = locked_account
+            .total_gsrm_amount
+            .checked_add(source.total_gsrm_amount)
+            .ok_or(SerumGovError::MathOverflow)?;
+        locked_account.gsrm_burned = locked_account
+            .gsrm_burned
+            .checked_add(source.gsrm_burned)
+            .ok_or(SerumGovError::MathOverflow)?;
+        // Merging can't shorten a lockup, or skip the min position age.
+        locked_account.lockup_end = cmp::max(locked_account.lockup_end, source.lockup_end);
+        locked_account.created_at = cmp::max(locked_account.created_at, source.created_at);
+
+        position_history.record(
+            PositionActionKind::Merge,
+            source
+                .total_gsrm_amount
+                .checked_sub(source.gsrm_burned)
+                .ok_or(SerumGovError::MathUnderflow)?,
+            source_info.key(),
+            now,
+        )?;
+
+        let mut source_account: Account<LockedAccount> = Account::try_from(source_info)?;
+        source_account.status = PositionStatus::Closed;
+        source_account.exit(&crate::ID)?;
+        source_account.close(rent_payer.clone())?;
+
+        // Positions which weren't touched since PositionHistory was added don't have one.
+        if source_history.owner == &crate::ID && !source_history.data_is_empty() {
+            let source_history: Account<PositionHistory> = Account::try_from(source_history)?;
+            source_history.close(rent_payer.clone())?;
+        }
+
+        merged.push(source_info.key());
+    }
+
+    msg!("Merged locked accounts: {:?}", merged);
+
+    let user_account = &mut ctx.accounts.owner_user_account;
+    user_account.closed_positions = user_account
+        .closed_positions
+        .checked_add(merged.len() as u64)
+        .ok_or(SerumGovError::MathOverflow)?;
+
+    let locked_account = &ctx.accounts.locked_account;
+    emit!(MergeLockedAccountsEvent {
+        owner,
+        locked_account: target,
+        merged,
+        total_gsrm_amount: locked_account.total_gsrm_amount,
+        gsrm_burned: locked_account.gsrm_burned,
+        lockup_end: locked_account.lockup_end,
+        timestamp: now,
+    });
+
+    Ok(())
+}
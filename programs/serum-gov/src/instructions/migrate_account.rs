@@ -0,0 +1,82 @@
+use anchor_lang::{prelude::*, system_program, Discriminator};
+
+use crate::errors::SerumGovError;
+use crate::events::MigrateAccountEvent;
+use crate::state::{ClaimTicket, Config, LockedAccount, User, VestAccount};
+
+/// Grows an account created by an older version of the program to its current size.
+/// NOTE: Fields are only ever appended, so the new fields are zero initialized.
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Any account owned by the program, type is checked in the handler.
+    #[account(
+        mut,
+        owner = crate::ID,
+    )]
+    pub account: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateAccount>) -> Result<()> {
+    let account = &ctx.accounts.account;
+
+    let discriminator: [u8; 8] = match account.try_borrow_data()?.get(..8) {
+        Some(discriminator) => discriminator
+            .try_into()
+            .map_err(|_| SerumGovError::InvalidMigrationAccount)?,
+        None => return err!(SerumGovError::InvalidMigrationAccount),
+    };
+
+    let new_len = if discriminator == Config::discriminator() {
+        Config::LEN
+    } else if discriminator == User::discriminator() {
+        User::LEN
+    } else if discriminator == LockedAccount::discriminator() {
+        LockedAccount::LEN
+    } else if discriminator == VestAccount::discriminator() {
+        VestAccount::LEN
+    } else if discriminator == ClaimTicket::discriminator() {
+        ClaimTicket::LEN
+    } else {
+        return err!(SerumGovError::InvalidMigrationAccount);
+    };
+
+    if account.data_len() >= new_len {
+        return err!(SerumGovError::AlreadyMigrated);
+    }
+
+    msg!("Migrating account: {:?}", (account.data_len(), new_len));
+
+    // Topping up lamports to keep the account rent exempt.
+    let lamports = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(account.lamports());
+
+    if lamports > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            lamports,
+        )?;
+    }
+
+    let old_len = account.data_len();
+    account.realloc(new_len, true)?;
+
+    emit!(MigrateAccountEvent {
+        account: account.key(),
+        old_len: old_len as u64,
+        new_len: new_len as u64,
+    });
+
+    Ok(())
+}
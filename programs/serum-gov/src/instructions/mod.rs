@@ -1,27 +1,73 @@
+pub mod audit_vaults;
 pub mod burn_locked_gsrm;
 pub mod burn_vest_gsrm;
 pub mod claim;
+pub mod close_position;
+pub mod close_user;
+pub mod create_max_voter_weight_record;
+pub mod create_voter_weight_record;
+pub mod delegate;
 pub mod deposit_locked_msrm;
 pub mod deposit_locked_srm;
 pub mod deposit_vest_msrm;
 pub mod deposit_vest_srm;
+pub mod extend_lock;
+pub mod get_unburned_gsrm_at;
+pub mod increase_locked_msrm;
+pub mod increase_locked_srm;
 pub mod init;
+pub mod init_stats;
 pub mod init_user;
+pub mod merge_locked_accounts;
+pub mod migrate_account;
 pub mod redeem_msrm;
 pub mod redeem_srm;
+pub mod split_locked_account;
+pub mod undelegate;
 pub mod update_config_authority;
 pub mod update_config_params;
+pub mod update_governance_config;
+pub mod update_lock_tiers;
+pub mod update_lockup_config;
+pub mod update_max_voter_weight_record;
+pub mod update_min_position_age;
+pub mod update_vest_weight_config;
+pub mod update_voter_weight_record;
+pub mod update_weight_curve;
 
+pub use audit_vaults::*;
 pub use burn_locked_gsrm::*;
 pub use burn_vest_gsrm::*;
 pub use claim::*;
+pub use close_position::*;
+pub use close_user::*;
+pub use create_max_voter_weight_record::*;
+pub use create_voter_weight_record::*;
+pub use delegate::*;
 pub use deposit_locked_msrm::*;
 pub use deposit_locked_srm::*;
 pub use deposit_vest_msrm::*;
 pub use deposit_vest_srm::*;
+pub use extend_lock::*;
+pub use get_unburned_gsrm_at::*;
+pub use increase_locked_msrm::*;
+pub use increase_locked_srm::*;
 pub use init::*;
+pub use init_stats::*;
 pub use init_user::*;
+pub use merge_locked_accounts::*;
+pub use migrate_account::*;
 pub use redeem_msrm::*;
 pub use redeem_srm::*;
+pub use split_locked_account::*;
+pub use undelegate::*;
 pub use update_config_authority::*;
 pub use update_config_params::*;
+pub use update_governance_config::*;
+pub use update_lock_tiers::*;
+pub use update_lockup_config::*;
+pub use update_max_voter_weight_record::*;
+pub use update_min_position_age::*;
+pub use update_vest_weight_config::*;
+pub use update_voter_weight_record::*;
+pub use update_weight_curve::*;
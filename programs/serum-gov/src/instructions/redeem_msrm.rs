@@ -1,8 +1,10 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, AccountsExit};
 use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
 
+use crate::conservation::check_redeem;
 use crate::errors::*;
-use crate::state::{Config, RedeemTicket};
+use crate::events::RedeemEvent;
+use crate::state::{Config, PositionActionKind, PositionHistory, RedeemTicket, Stats, User};
 
 #[derive(Accounts)]
 pub struct RedeemMSRM<'info> {
@@ -22,18 +24,42 @@ pub struct RedeemMSRM<'info> {
     )]
     pub config: Account<'info, Config>,
 
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [b"stats"],
+        bump,
+        space = Stats::LEN
+    )]
+    pub stats: Box<Account<'info, Stats>>,
+
+    #[account(
+        mut,
+        seeds = [b"user", &owner.key().to_bytes()[..]],
+        bump,
+    )]
+    pub owner_user_account: Box<Account<'info, User>>,
+
     #[account(
         mut,
         seeds = [b"redeem_ticket", &redeem_ticket.deposit_account.to_bytes()[..], redeem_ticket.redeem_index.to_le_bytes().as_ref()],
         bump,
         constraint = redeem_ticket.owner.key() == owner.key() @ SerumGovError::InvalidTicketOwner,
         constraint = redeem_ticket.is_msrm == true @ SerumGovError::InvalidRedeemTicket,
-        constraint = (redeem_ticket.created_at + redeem_ticket.redeem_delay) <= clock.unix_timestamp @ SerumGovError::TicketNotClaimable,
+        constraint = redeem_ticket.created_at.checked_add(redeem_ticket.redeem_delay).ok_or(SerumGovError::MathOverflow)? <= clock.unix_timestamp @ SerumGovError::TicketNotClaimable,
         constraint = redeem_ticket.amount > 0 @ SerumGovError::TicketNotClaimable,
         close = owner
     )]
     pub redeem_ticket: Account<'info, RedeemTicket>,
 
+    /// CHECK: PositionHistory of the RedeemTicket's position, which is only recorded into if it exists.
+    #[account(
+        mut,
+        seeds = [b"position_history", &redeem_ticket.deposit_account.to_bytes()[..]],
+        bump,
+    )]
+    pub position_history: AccountInfo<'info>,
+
     #[account(address = config.msrm_mint)]
     pub msrm_mint: Account<'info, Mint>,
 
@@ -71,6 +97,8 @@ impl<'info> RedeemMSRM<'info> {
 }
 
 pub fn handler(ctx: Context<RedeemMSRM>) -> Result<()> {
+    let vault_amount = ctx.accounts.msrm_vault.amount;
+
     token::transfer(
         ctx.accounts
             .into_redeem_msrm_context()
@@ -79,7 +107,41 @@ pub fn handler(ctx: Context<RedeemMSRM>) -> Result<()> {
     )?;
 
     let redeem_ticket = &mut ctx.accounts.redeem_ticket;
+    let amount = redeem_ticket.amount;
     redeem_ticket.amount = 0;
 
+    ctx.accounts.msrm_vault.reload()?;
+    check_redeem(vault_amount, ctx.accounts.msrm_vault.amount, amount)?;
+
+    let stats = &mut ctx.accounts.stats;
+    stats.initialize(*ctx.bumps.get("stats").unwrap());
+    stats.record_redeem(true, amount);
+
+    ctx.accounts.owner_user_account.record_redeem(true, amount);
+
+    // NOTE: Not created here, since positions closed by burns before PositionHistory was added
+    // never had one, and nothing could close it. Positions burned since then have one.
+    let position_history = &ctx.accounts.position_history;
+    if position_history.owner == &crate::ID && !position_history.data_is_empty() {
+        let mut position_history: Account<PositionHistory> = Account::try_from(position_history)?;
+        position_history.record(
+            PositionActionKind::Redeem,
+            amount,
+            ctx.accounts.redeem_ticket.key(),
+            ctx.accounts.clock.unix_timestamp,
+        )?;
+        position_history.exit(&crate::ID)?;
+    }
+
+    emit!(RedeemEvent {
+        owner: ctx.accounts.owner.key(),
+        redeem_ticket: ctx.accounts.redeem_ticket.key(),
+        deposit_account: ctx.accounts.redeem_ticket.deposit_account,
+        redeem_index: ctx.accounts.redeem_ticket.redeem_index,
+        is_msrm: ctx.accounts.redeem_ticket.is_msrm,
+        amount,
+        timestamp: ctx.accounts.clock.unix_timestamp,
+    });
+
     Ok(())
 }
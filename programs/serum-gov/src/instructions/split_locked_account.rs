@@ -0,0 +1,150 @@
+use anchor_lang::prelude::*;
+
+use crate::conservation::redeem_amount;
+use crate::errors::SerumGovError;
+use crate::events::SplitLockedAccountEvent;
+use crate::state::{LockedAccount, PositionActionKind, PositionHistory, PositionStatus, User};
+
+/// Moves unburned gSRM from `locked_account` into a new LockedAccount at the owner's next `lock_index`.
+/// NOTE: The new LockedAccount has the same lockup, lock tier and age as the one it was split from.
+#[derive(Accounts)]
+pub struct SplitLockedAccount<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user", &owner.key().to_bytes()[..]],
+        bump,
+    )]
+    pub owner_user_account: Box<Account<'info, User>>,
+
+    #[account(
+        mut,
+        seeds = [b"locked_account", &owner.key().to_bytes()[..], locked_account.lock_index.to_le_bytes().as_ref()],
+        bump,
+        constraint = locked_account.status == PositionStatus::Active @ SerumGovError::PositionNotActive,
+    )]
+    pub locked_account: Box<Account<'info, LockedAccount>>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"locked_account", &owner.key().to_bytes()[..], owner_user_account.lock_index.to_le_bytes().as_ref()],
+        bump,
+        space = LockedAccount::LEN
+    )]
+    pub new_locked_account: Box<Account<'info, LockedAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [b"position_history", &locked_account.key().to_bytes()[..]],
+        bump,
+        space = PositionHistory::LEN
+    )]
+    pub position_history: Box<Account<'info, PositionHistory>>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"position_history", &new_locked_account.key().to_bytes()[..]],
+        bump,
+        space = PositionHistory::LEN
+    )]
+    pub new_position_history: Box<Account<'info, PositionHistory>>,
+
+    pub clock: Sysvar<'info, Clock>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SplitLockedAccount>, gsrm_amount: u64) -> Result<()> {
+    if gsrm_amount == 0 {
+        return Err(ProgramError::InvalidInstructionData.into());
+    }
+
+    // CHECK: MSRM positions can only be split in whole MSRM.
+    redeem_amount(gsrm_amount, ctx.accounts.locked_account.is_msrm)?;
+
+    let locked_account = &mut ctx.accounts.locked_account;
+
+    // CHECK: Only unburned gSRM can be moved, so `gsrm_burned` stays with the original position.
+    let unburned = locked_account
+        .total_gsrm_amount
+        .checked_sub(locked_account.gsrm_burned)
+        .ok_or(SerumGovError::MathUnderflow)?;
+    if gsrm_amount > unburned {
+        return err!(SerumGovError::InvalidGSRMAmount);
+    }
+
+    msg!("Splitting locked account: {:?}", (unburned, gsrm_amount));
+
+    locked_account.total_gsrm_amount = locked_account
+        .total_gsrm_amount
+        .checked_sub(gsrm_amount)
+        .ok_or(SerumGovError::MathUnderflow)?;
+
+    if locked_account.gsrm_burned == locked_account.total_gsrm_amount {
+        locked_account.status = PositionStatus::FullyBurned;
+    }
+
+    let user_account = &mut ctx.accounts.owner_user_account;
+
+    let new_locked_account = &mut ctx.accounts.new_locked_account;
+    new_locked_account.owner = ctx.accounts.owner.key();
+    new_locked_account.bump = *ctx.bumps.get("new_locked_account").unwrap();
+    new_locked_account.lock_index = user_account.lock_index;
+    new_locked_account.redeem_index = 0;
+    new_locked_account.is_msrm = locked_account.is_msrm;
+    new_locked_account.created_at = locked_account.created_at;
+    new_locked_account.total_gsrm_amount = gsrm_amount;
+    new_locked_account.gsrm_burned = 0;
+    new_locked_account.lockup_end = locked_account.lockup_end;
+    new_locked_account.status = PositionStatus::Active;
+    new_locked_account.rent_payer = ctx.accounts.owner.key();
+    new_locked_account.lock_tier = locked_account.lock_tier;
+    new_locked_account.lock_multiplier = locked_account.lock_multiplier;
+
+    user_account.lock_index = user_account
+        .lock_index
+        .checked_add(1)
+        .ok_or(SerumGovError::MathOverflow)?;
+
+    let now = ctx.accounts.clock.unix_timestamp;
+
+    let position_history = &mut ctx.accounts.position_history;
+    position_history.initialize(
+        locked_account.key(),
+        *ctx.bumps.get("position_history").unwrap(),
+    );
+    position_history.record(
+        PositionActionKind::Split,
+        gsrm_amount,
+        new_locked_account.key(),
+        now,
+    )?;
+
+    let new_position_history = &mut ctx.accounts.new_position_history;
+    new_position_history.initialize(
+        new_locked_account.key(),
+        *ctx.bumps.get("new_position_history").unwrap(),
+    );
+    new_position_history.record(
+        PositionActionKind::Split,
+        gsrm_amount,
+        locked_account.key(),
+        now,
+    )?;
+
+    emit!(SplitLockedAccountEvent {
+        owner: ctx.accounts.owner.key(),
+        locked_account: locked_account.key(),
+        new_locked_account: new_locked_account.key(),
+        new_lock_index: new_locked_account.lock_index,
+        is_msrm: new_locked_account.is_msrm,
+        gsrm_amount,
+        timestamp: now,
+    });
+
+    Ok(())
+}
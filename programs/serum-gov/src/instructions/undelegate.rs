@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::events::UndelegateEvent;
+use crate::governance::check_no_active_votes;
+use crate::state::{Config, User};
+
+/// NOTE: TokenOwnerRecords of the owner and of the current delegate must be passed in
+/// as `remaining_accounts` if checked in Config.
+#[derive(Accounts)]
+pub struct Undelegate<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"user", &owner.key().to_bytes()[..]],
+        bump,
+    )]
+    pub owner_user_account: Account<'info, User>,
+}
+
+pub fn handler(ctx: Context<Undelegate>) -> Result<()> {
+    // CHECK: Positions can't be taken back while the delegate has a live vote cast with them.
+    check_no_active_votes(
+        &ctx.accounts.config,
+        &ctx.accounts.owner_user_account.voters(),
+        ctx.remaining_accounts,
+    )?;
+
+    msg!("Undelegated voting power");
+
+    let user_account = &mut ctx.accounts.owner_user_account;
+    let old_delegate = user_account.delegate;
+    user_account.delegate = Pubkey::default();
+
+    emit!(UndelegateEvent {
+        owner: user_account.owner,
+        old_delegate,
+    });
+
+    Ok(())
+}
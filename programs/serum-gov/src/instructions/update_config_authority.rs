@@ -1,9 +1,11 @@
 use anchor_lang::prelude::*;
 
-use crate::state::Config;
+use crate::events::UpdateConfigAuthorityEvent;
+use crate::state::{Config, ConfigField, ConfigHistory, ConfigValue};
 
 #[derive(Accounts)]
 pub struct UpdateConfigAuthority<'info> {
+    #[account(mut)]
     pub config_authority: Signer<'info>,
 
     #[account(
@@ -13,12 +15,42 @@ pub struct UpdateConfigAuthority<'info> {
         has_one = config_authority
     )]
     pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = config_authority,
+        seeds = [b"config_history"],
+        bump,
+        space = ConfigHistory::LEN
+    )]
+    pub config_history: Box<Account<'info, ConfigHistory>>,
+
+    pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<UpdateConfigAuthority>, new_config_authority: Pubkey) -> Result<()> {
     msg!("Updated config authority: {:?}", new_config_authority);
 
     let config = &mut ctx.accounts.config;
+
+    let signer = ctx.accounts.config_authority.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    let config_history = &mut ctx.accounts.config_history;
+    config_history.bump = *ctx.bumps.get("config_history").unwrap();
+    config_history.record(
+        ConfigField::ConfigAuthority,
+        ConfigValue::Pubkey(config.config_authority),
+        ConfigValue::Pubkey(new_config_authority),
+        signer,
+        timestamp,
+    )?;
+
+    let old_config_authority = config.config_authority;
     config.config_authority = new_config_authority;
+
+    emit!(UpdateConfigAuthorityEvent {
+        old_config_authority,
+        new_config_authority,
+    });
     Ok(())
 }
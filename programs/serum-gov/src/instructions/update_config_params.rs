@@ -1,9 +1,11 @@
 use anchor_lang::prelude::*;
 
-use crate::state::Config;
+use crate::events::UpdateConfigParamsEvent;
+use crate::state::{Config, ConfigField, ConfigHistory, ConfigValue};
 
 #[derive(Accounts)]
 pub struct UpdateConfigParams<'info> {
+    #[account(mut)]
     pub config_authority: Signer<'info>,
 
     #[account(
@@ -13,6 +15,17 @@ pub struct UpdateConfigParams<'info> {
         has_one = config_authority
     )]
     pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = config_authority,
+        seeds = [b"config_history"],
+        bump,
+        space = ConfigHistory::LEN
+    )]
+    pub config_history: Box<Account<'info, ConfigHistory>>,
+
+    pub system_program: Program<'info, System>,
 }
 
 pub fn handler(
@@ -22,6 +35,13 @@ pub fn handler(
     cliff_period: i64,
     linear_vesting_period: i64,
 ) -> Result<()> {
+    Config::validate_params(
+        claim_delay,
+        redeem_delay,
+        cliff_period,
+        linear_vesting_period,
+    )?;
+
     msg!(
         "Updated config params: {:?}",
         (
@@ -33,10 +53,51 @@ pub fn handler(
     );
 
     let config = &mut ctx.accounts.config;
+
+    let signer = ctx.accounts.config_authority.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    let config_history = &mut ctx.accounts.config_history;
+    config_history.bump = *ctx.bumps.get("config_history").unwrap();
+    config_history.record(
+        ConfigField::ClaimDelay,
+        ConfigValue::I64(config.claim_delay),
+        ConfigValue::I64(claim_delay),
+        signer,
+        timestamp,
+    )?;
+    config_history.record(
+        ConfigField::RedeemDelay,
+        ConfigValue::I64(config.redeem_delay),
+        ConfigValue::I64(redeem_delay),
+        signer,
+        timestamp,
+    )?;
+    config_history.record(
+        ConfigField::CliffPeriod,
+        ConfigValue::I64(config.cliff_period),
+        ConfigValue::I64(cliff_period),
+        signer,
+        timestamp,
+    )?;
+    config_history.record(
+        ConfigField::LinearVestingPeriod,
+        ConfigValue::I64(config.linear_vesting_period),
+        ConfigValue::I64(linear_vesting_period),
+        signer,
+        timestamp,
+    )?;
+
     config.claim_delay = claim_delay;
     config.redeem_delay = redeem_delay;
     config.cliff_period = cliff_period;
     config.linear_vesting_period = linear_vesting_period;
 
+    emit!(UpdateConfigParamsEvent {
+        claim_delay,
+        redeem_delay,
+        cliff_period,
+        linear_vesting_period,
+    });
+
     Ok(())
 }
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+
+use crate::events::UpdateGovernanceConfigEvent;
+use crate::state::{Config, ConfigField, ConfigHistory, ConfigValue};
+
+#[derive(Accounts)]
+pub struct UpdateGovernanceConfig<'info> {
+    #[account(mut)]
+    pub config_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        has_one = config_authority
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = config_authority,
+        seeds = [b"config_history"],
+        bump,
+        space = ConfigHistory::LEN
+    )]
+    pub config_history: Box<Account<'info, ConfigHistory>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// NOTE: Setting `governance_program` to Pubkey::default() disables the check in burn instructions.
+pub fn handler(
+    ctx: Context<UpdateGovernanceConfig>,
+    governance_program: Pubkey,
+    realm: Pubkey,
+    governing_token_mint: Pubkey,
+) -> Result<()> {
+    msg!(
+        "Updated governance config: {:?}",
+        (governance_program, realm, governing_token_mint)
+    );
+
+    let config = &mut ctx.accounts.config;
+
+    let signer = ctx.accounts.config_authority.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    let config_history = &mut ctx.accounts.config_history;
+    config_history.bump = *ctx.bumps.get("config_history").unwrap();
+    config_history.record(
+        ConfigField::GovernanceProgram,
+        ConfigValue::Pubkey(config.governance_program),
+        ConfigValue::Pubkey(governance_program),
+        signer,
+        timestamp,
+    )?;
+    config_history.record(
+        ConfigField::Realm,
+        ConfigValue::Pubkey(config.realm),
+        ConfigValue::Pubkey(realm),
+        signer,
+        timestamp,
+    )?;
+    config_history.record(
+        ConfigField::GoverningTokenMint,
+        ConfigValue::Pubkey(config.governing_token_mint),
+        ConfigValue::Pubkey(governing_token_mint),
+        signer,
+        timestamp,
+    )?;
+
+    config.governance_program = governance_program;
+    config.realm = realm;
+    config.governing_token_mint = governing_token_mint;
+
+    emit!(UpdateGovernanceConfigEvent {
+        governance_program,
+        realm,
+        governing_token_mint,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use std::cmp;
+
+use anchor_lang::prelude::*;
+
+use crate::errors::SerumGovError;
+use crate::events::UpdateLockTiersEvent;
+use crate::state::{Config, ConfigField, ConfigHistory, ConfigValue, LockTier, MAX_LOCK_TIERS};
+use crate::BASIS_POINTS;
+
+/// Replaces the lock tiers deposits can pick from.
+/// NOTE: Positions keep the lockup end and multiplier of the tier at the time of their deposit,
+/// so `max_lock_multiplier` in Config never goes down, even if a tier is lowered or removed.
+#[derive(Accounts)]
+pub struct UpdateLockTiers<'info> {
+    #[account(mut)]
+    pub config_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        has_one = config_authority
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = config_authority,
+        seeds = [b"config_history"],
+        bump,
+        space = ConfigHistory::LEN
+    )]
+    pub config_history: Box<Account<'info, ConfigHistory>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<UpdateLockTiers>, lock_tiers: Vec<LockTier>) -> Result<()> {
+    if lock_tiers.len() > MAX_LOCK_TIERS
+        || lock_tiers
+            .iter()
+            .any(|tier| tier.duration <= 0 || tier.multiplier < BASIS_POINTS)
+    {
+        return err!(SerumGovError::InvalidLockTiers);
+    }
+
+    msg!("Updated lock tiers: {:?}", lock_tiers);
+
+    let mut new_lock_tiers = [LockTier::default(); MAX_LOCK_TIERS];
+    new_lock_tiers[..lock_tiers.len()].copy_from_slice(&lock_tiers);
+
+    let config = &mut ctx.accounts.config;
+
+    let signer = ctx.accounts.config_authority.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    let config_history = &mut ctx.accounts.config_history;
+    config_history.bump = *ctx.bumps.get("config_history").unwrap();
+    for (index, (old_tier, new_tier)) in config.lock_tiers.iter().zip(&new_lock_tiers).enumerate() {
+        config_history.record(
+            ConfigField::LockTiers,
+            ConfigValue::LockTier {
+                index: index as u8,
+                duration: old_tier.duration,
+                multiplier: old_tier.multiplier,
+            },
+            ConfigValue::LockTier {
+                index: index as u8,
+                duration: new_tier.duration,
+                multiplier: new_tier.multiplier,
+            },
+            signer,
+            timestamp,
+        )?;
+    }
+
+    // Old tiers count too, for Configs migrated before `max_lock_multiplier` was tracked.
+    config.max_lock_multiplier = config
+        .lock_tiers
+        .iter()
+        .chain(&new_lock_tiers)
+        .map(|tier| tier.multiplier)
+        .fold(config.max_lock_multiplier, cmp::max);
+    msg!("Max lock multiplier: {}", config.max_lock_multiplier);
+
+    config.lock_tiers = new_lock_tiers;
+
+    emit!(UpdateLockTiersEvent {
+        lock_tiers,
+        max_lock_multiplier: config.max_lock_multiplier,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SerumGovError;
+use crate::events::UpdateLockupConfigEvent;
+use crate::state::{Config, ConfigField, ConfigHistory, ConfigValue};
+use crate::BASIS_POINTS;
+
+#[derive(Accounts)]
+pub struct UpdateLockupConfig<'info> {
+    #[account(mut)]
+    pub config_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        has_one = config_authority
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = config_authority,
+        seeds = [b"config_history"],
+        bump,
+        space = ConfigHistory::LEN
+    )]
+    pub config_history: Box<Account<'info, ConfigHistory>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateLockupConfig>,
+    max_lockup_period: i64,
+    max_lockup_multiplier: u64,
+) -> Result<()> {
+    if max_lockup_period < 0 || max_lockup_multiplier < BASIS_POINTS {
+        return err!(SerumGovError::InvalidLockupConfig);
+    }
+
+    msg!(
+        "Updated lockup config: {:?}",
+        (max_lockup_period, max_lockup_multiplier)
+    );
+
+    let config = &mut ctx.accounts.config;
+
+    let signer = ctx.accounts.config_authority.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    let config_history = &mut ctx.accounts.config_history;
+    config_history.bump = *ctx.bumps.get("config_history").unwrap();
+    config_history.record(
+        ConfigField::MaxLockupPeriod,
+        ConfigValue::I64(config.max_lockup_period),
+        ConfigValue::I64(max_lockup_period),
+        signer,
+        timestamp,
+    )?;
+    config_history.record(
+        ConfigField::MaxLockupMultiplier,
+        ConfigValue::U64(config.max_lockup_multiplier),
+        ConfigValue::U64(max_lockup_multiplier),
+        signer,
+        timestamp,
+    )?;
+
+    config.max_lockup_period = max_lockup_period;
+    config.max_lockup_multiplier = max_lockup_multiplier;
+
+    emit!(UpdateLockupConfigEvent {
+        max_lockup_period,
+        max_lockup_multiplier,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SerumGovError;
+use crate::events::MaxVoterWeightRecordEvent;
+use crate::state::{Config, MaxVoterWeightRecord, Stats};
+use crate::voter_weight::max_voter_weight;
+
+#[derive(Accounts)]
+pub struct UpdateMaxVoterWeightRecord<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"max_voter_weight_record", &max_voter_weight_record.realm.to_bytes()[..], &max_voter_weight_record.governing_token_mint.to_bytes()[..]],
+        bump,
+    )]
+    pub max_voter_weight_record: Account<'info, MaxVoterWeightRecord>,
+
+    #[account(
+        seeds = [b"stats"],
+        bump = stats.bump
+    )]
+    pub stats: Account<'info, Stats>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<UpdateMaxVoterWeightRecord>) -> Result<()> {
+    // NOTE: Only SRM and MSRM backing positions count, not vault balances, which anyone can transfer
+    // tokens into and which include RedeemTickets.
+    let stats = &ctx.accounts.stats;
+    let srm_amount = stats
+        .srm_locked
+        .checked_add(stats.srm_vesting)
+        .ok_or(SerumGovError::MathOverflow)?;
+    let msrm_amount = stats
+        .msrm_locked
+        .checked_add(stats.msrm_vesting)
+        .ok_or(SerumGovError::MathOverflow)?;
+
+    let max_voter_weight = max_voter_weight(&ctx.accounts.config, srm_amount, msrm_amount)?;
+    msg!("Max voter weight: {}", max_voter_weight);
+
+    let max_voter_weight_record = &mut ctx.accounts.max_voter_weight_record;
+    max_voter_weight_record.max_voter_weight = max_voter_weight;
+    max_voter_weight_record.max_voter_weight_expiry = Some(ctx.accounts.clock.slot);
+
+    emit!(MaxVoterWeightRecordEvent {
+        max_voter_weight_record: max_voter_weight_record.key(),
+        realm: max_voter_weight_record.realm,
+        governing_token_mint: max_voter_weight_record.governing_token_mint,
+        max_voter_weight,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SerumGovError;
+use crate::events::UpdateMinPositionAgeEvent;
+use crate::state::{Config, ConfigField, ConfigHistory, ConfigValue};
+
+#[derive(Accounts)]
+pub struct UpdateMinPositionAge<'info> {
+    #[account(mut)]
+    pub config_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        has_one = config_authority
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = config_authority,
+        seeds = [b"config_history"],
+        bump,
+        space = ConfigHistory::LEN
+    )]
+    pub config_history: Box<Account<'info, ConfigHistory>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<UpdateMinPositionAge>, min_position_age: i64) -> Result<()> {
+    if min_position_age < 0 {
+        return err!(SerumGovError::InvalidMinPositionAge);
+    }
+
+    msg!("Updated min position age: {:?}", min_position_age);
+
+    let config = &mut ctx.accounts.config;
+
+    let signer = ctx.accounts.config_authority.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    let config_history = &mut ctx.accounts.config_history;
+    config_history.bump = *ctx.bumps.get("config_history").unwrap();
+    config_history.record(
+        ConfigField::MinPositionAge,
+        ConfigValue::I64(config.min_position_age),
+        ConfigValue::I64(min_position_age),
+        signer,
+        timestamp,
+    )?;
+
+    config.min_position_age = min_position_age;
+
+    emit!(UpdateMinPositionAgeEvent { min_position_age });
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SerumGovError;
+use crate::events::UpdateVestWeightConfigEvent;
+use crate::state::{Config, ConfigField, ConfigHistory, ConfigValue, VestWeightMode};
+use crate::BASIS_POINTS;
+
+#[derive(Accounts)]
+pub struct UpdateVestWeightConfig<'info> {
+    #[account(mut)]
+    pub config_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        has_one = config_authority
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = config_authority,
+        seeds = [b"config_history"],
+        bump,
+        space = ConfigHistory::LEN
+    )]
+    pub config_history: Box<Account<'info, ConfigHistory>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateVestWeightConfig>,
+    vest_weight_mode: VestWeightMode,
+    unvested_weight: u64,
+) -> Result<()> {
+    // CHECK: Unvested gSRM can't count for more than vested gSRM.
+    if unvested_weight > BASIS_POINTS {
+        return err!(SerumGovError::InvalidVestWeightConfig);
+    }
+
+    msg!(
+        "Updated vest weight config: {:?}",
+        (vest_weight_mode, unvested_weight)
+    );
+
+    let config = &mut ctx.accounts.config;
+
+    let signer = ctx.accounts.config_authority.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    let config_history = &mut ctx.accounts.config_history;
+    config_history.bump = *ctx.bumps.get("config_history").unwrap();
+    config_history.record(
+        ConfigField::VestWeightMode,
+        ConfigValue::U64(config.vest_weight_mode as u64),
+        ConfigValue::U64(vest_weight_mode as u64),
+        signer,
+        timestamp,
+    )?;
+    config_history.record(
+        ConfigField::UnvestedWeight,
+        ConfigValue::U64(config.unvested_weight),
+        ConfigValue::U64(unvested_weight),
+        signer,
+        timestamp,
+    )?;
+
+    config.vest_weight_mode = vest_weight_mode;
+    config.unvested_weight = unvested_weight;
+
+    emit!(UpdateVestWeightConfigEvent {
+        vest_weight_mode,
+        unvested_weight,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::events::VoterWeightRecordEvent;
+use crate::state::{Config, VoterWeightRecord};
+use crate::voter_weight::voter_weight_from_positions;
+
+/// NOTE: User accounts of the owner and their delegators, each followed by their LockedAccounts
+/// and VestAccounts, must be passed in as `remaining_accounts`.
+#[derive(Accounts)]
+pub struct UpdateVoterWeightRecord<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"voter_weight_record", &voter_weight_record.realm.to_bytes()[..], &voter_weight_record.governing_token_mint.to_bytes()[..], &voter_weight_record.governing_token_owner.to_bytes()[..]],
+        bump,
+    )]
+    pub voter_weight_record: Account<'info, VoterWeightRecord>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<UpdateVoterWeightRecord>) -> Result<()> {
+    msg!("Min position age: {}", ctx.accounts.config.min_position_age);
+
+    let voter_weight = voter_weight_from_positions(
+        ctx.remaining_accounts,
+        &ctx.accounts.voter_weight_record.governing_token_owner,
+        &ctx.accounts.config,
+        ctx.accounts.clock.unix_timestamp,
+    )?;
+    msg!("Voter weight: {}", voter_weight);
+
+    let voter_weight_record = &mut ctx.accounts.voter_weight_record;
+    voter_weight_record.voter_weight = voter_weight;
+    // Weight is only valid for the current slot, positions can change right after.
+    voter_weight_record.voter_weight_expiry = Some(ctx.accounts.clock.slot);
+    voter_weight_record.weight_action = None;
+    voter_weight_record.weight_action_target = None;
+
+    emit!(VoterWeightRecordEvent {
+        voter_weight_record: voter_weight_record.key(),
+        realm: voter_weight_record.realm,
+        governing_token_mint: voter_weight_record.governing_token_mint,
+        governing_token_owner: voter_weight_record.governing_token_owner,
+        voter_weight,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::SerumGovError;
+use crate::events::UpdateWeightCurveEvent;
+use crate::state::{Config, ConfigField, ConfigHistory, ConfigValue, WeightCurve};
+
+/// NOTE: The MaxVoterWeightRecord can't be updated while the curve isn't Linear.
+#[derive(Accounts)]
+pub struct UpdateWeightCurve<'info> {
+    #[account(mut)]
+    pub config_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        has_one = config_authority
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = config_authority,
+        seeds = [b"config_history"],
+        bump,
+        space = ConfigHistory::LEN
+    )]
+    pub config_history: Box<Account<'info, ConfigHistory>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateWeightCurve>,
+    weight_curve: WeightCurve,
+    max_owner_weight: u64,
+) -> Result<()> {
+    // CHECK: A cap of 0 would take away everyone's voting weight.
+    if weight_curve == WeightCurve::Capped && max_owner_weight == 0 {
+        return err!(SerumGovError::InvalidWeightCurveConfig);
+    }
+
+    msg!(
+        "Updated weight curve: {:?}",
+        (weight_curve, max_owner_weight)
+    );
+
+    let config = &mut ctx.accounts.config;
+
+    let signer = ctx.accounts.config_authority.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    let config_history = &mut ctx.accounts.config_history;
+    config_history.bump = *ctx.bumps.get("config_history").unwrap();
+    config_history.record(
+        ConfigField::WeightCurve,
+        ConfigValue::U64(config.weight_curve as u64),
+        ConfigValue::U64(weight_curve as u64),
+        signer,
+        timestamp,
+    )?;
+    config_history.record(
+        ConfigField::MaxOwnerWeight,
+        ConfigValue::U64(config.max_owner_weight),
+        ConfigValue::U64(max_owner_weight),
+        signer,
+        timestamp,
+    )?;
+
+    config.weight_curve = weight_curve;
+    config.max_owner_weight = max_owner_weight;
+
+    emit!(UpdateWeightCurveEvent {
+        weight_curve,
+        max_owner_weight,
+    });
+
+    Ok(())
+}
@@ -3,13 +3,21 @@ use anchor_lang::prelude::*;
 declare_id!("FBcTbv5rLy7MQkkAU2uDzAEjjZDeu2BVLVRJGxyz6hnV");
 
 pub mod config;
+pub mod conservation;
 pub mod errors;
+pub mod events;
+pub mod governance;
 pub mod instructions;
 pub mod state;
+pub mod voter_weight;
 
 pub use instructions::*;
+use state::{LockTier, VestWeightMode, WeightCurve};
 
 const MSRM_MULTIPLIER: u64 = 1_000_000_000_000;
+const BASIS_POINTS: u64 = 10_000;
+/// gSRM has the same decimals as SRM.
+const GSRM_UNIT: u64 = 1_000_000;
 
 #[program]
 pub mod serum_gov {
@@ -38,6 +46,29 @@ pub mod serum_gov {
         )
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn init_stats(
+        ctx: Context<InitStats>,
+        srm_locked: u64,
+        msrm_locked: u64,
+        srm_vesting: u64,
+        msrm_vesting: u64,
+        gsrm_unclaimed: u64,
+        srm_redeemable: u64,
+        msrm_redeemable: u64,
+    ) -> Result<()> {
+        init_stats::handler(
+            ctx,
+            srm_locked,
+            msrm_locked,
+            srm_vesting,
+            msrm_vesting,
+            gsrm_unclaimed,
+            srm_redeemable,
+            msrm_redeemable,
+        )
+    }
+
     pub fn update_config_authority(
         ctx: Context<UpdateConfigAuthority>,
         new_config_authority: Pubkey,
@@ -61,16 +92,111 @@ pub mod serum_gov {
         )
     }
 
+    pub fn update_lockup_config(
+        ctx: Context<UpdateLockupConfig>,
+        max_lockup_period: i64,
+        max_lockup_multiplier: u64,
+    ) -> Result<()> {
+        update_lockup_config::handler(ctx, max_lockup_period, max_lockup_multiplier)
+    }
+
+    pub fn update_vest_weight_config(
+        ctx: Context<UpdateVestWeightConfig>,
+        vest_weight_mode: VestWeightMode,
+        unvested_weight: u64,
+    ) -> Result<()> {
+        update_vest_weight_config::handler(ctx, vest_weight_mode, unvested_weight)
+    }
+
+    pub fn update_governance_config(
+        ctx: Context<UpdateGovernanceConfig>,
+        governance_program: Pubkey,
+        realm: Pubkey,
+        governing_token_mint: Pubkey,
+    ) -> Result<()> {
+        update_governance_config::handler(ctx, governance_program, realm, governing_token_mint)
+    }
+
+    pub fn update_min_position_age(
+        ctx: Context<UpdateMinPositionAge>,
+        min_position_age: i64,
+    ) -> Result<()> {
+        update_min_position_age::handler(ctx, min_position_age)
+    }
+
+    pub fn update_weight_curve(
+        ctx: Context<UpdateWeightCurve>,
+        weight_curve: WeightCurve,
+        max_owner_weight: u64,
+    ) -> Result<()> {
+        update_weight_curve::handler(ctx, weight_curve, max_owner_weight)
+    }
+
+    pub fn update_lock_tiers(
+        ctx: Context<UpdateLockTiers>,
+        lock_tiers: Vec<LockTier>,
+    ) -> Result<()> {
+        update_lock_tiers::handler(ctx, lock_tiers)
+    }
+
+    pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+        migrate_account::handler(ctx)
+    }
+
     pub fn init_user(ctx: Context<InitUser>, owner: Pubkey) -> Result<()> {
         init_user::handler(ctx, owner)
     }
 
-    pub fn deposit_locked_srm(ctx: Context<DepositLockedSRM>, amount: u64) -> Result<()> {
-        deposit_locked_srm::handler(ctx, amount)
+    pub fn close_user(ctx: Context<CloseUser>) -> Result<()> {
+        close_user::handler(ctx)
+    }
+
+    pub fn delegate(ctx: Context<Delegate>, delegate: Pubkey) -> Result<()> {
+        delegate::handler(ctx, delegate)
+    }
+
+    pub fn undelegate(ctx: Context<Undelegate>) -> Result<()> {
+        undelegate::handler(ctx)
+    }
+
+    pub fn deposit_locked_srm(
+        ctx: Context<DepositLockedSRM>,
+        amount: u64,
+        lockup_end: i64,
+        lock_tier: u8,
+    ) -> Result<()> {
+        deposit_locked_srm::handler(ctx, amount, lockup_end, lock_tier)
+    }
+
+    pub fn deposit_locked_msrm(
+        ctx: Context<DepositLockedMSRM>,
+        amount: u64,
+        lockup_end: i64,
+        lock_tier: u8,
+    ) -> Result<()> {
+        deposit_locked_msrm::handler(ctx, amount, lockup_end, lock_tier)
+    }
+
+    pub fn increase_locked_srm(ctx: Context<IncreaseLockedSRM>, amount: u64) -> Result<()> {
+        increase_locked_srm::handler(ctx, amount)
     }
 
-    pub fn deposit_locked_msrm(ctx: Context<DepositLockedMSRM>, amount: u64) -> Result<()> {
-        deposit_locked_msrm::handler(ctx, amount)
+    pub fn increase_locked_msrm(ctx: Context<IncreaseLockedMSRM>, amount: u64) -> Result<()> {
+        increase_locked_msrm::handler(ctx, amount)
+    }
+
+    pub fn extend_lock(ctx: Context<ExtendLock>, lockup_end: i64) -> Result<()> {
+        extend_lock::handler(ctx, lockup_end)
+    }
+
+    pub fn merge_locked_accounts<'info>(
+        ctx: Context<'_, '_, '_, 'info, MergeLockedAccounts<'info>>,
+    ) -> Result<()> {
+        merge_locked_accounts::handler(ctx)
+    }
+
+    pub fn split_locked_account(ctx: Context<SplitLockedAccount>, gsrm_amount: u64) -> Result<()> {
+        split_locked_account::handler(ctx, gsrm_amount)
     }
 
     pub fn claim(ctx: Context<Claim>) -> Result<()> {
@@ -81,6 +207,10 @@ pub mod serum_gov {
         burn_locked_gsrm::handler(ctx, amount)
     }
 
+    pub fn close_position(ctx: Context<ClosePosition>) -> Result<()> {
+        close_position::handler(ctx)
+    }
+
     pub fn redeem_srm(ctx: Context<RedeemSRM>) -> Result<()> {
         redeem_srm::handler(ctx)
     }
@@ -100,4 +230,36 @@ pub mod serum_gov {
     pub fn burn_vest_gsrm(ctx: Context<BurnVestGSRM>, amount: u64) -> Result<()> {
         burn_vest_gsrm::handler(ctx, amount)
     }
+
+    pub fn audit_vaults(ctx: Context<AuditVaults>, strict: bool) -> Result<()> {
+        audit_vaults::handler(ctx, strict)
+    }
+
+    pub fn get_unburned_gsrm_at(ctx: Context<GetUnburnedGSRMAt>, timestamp: i64) -> Result<u64> {
+        get_unburned_gsrm_at::handler(ctx, timestamp)
+    }
+
+    pub fn create_voter_weight_record(
+        ctx: Context<CreateVoterWeightRecord>,
+        realm: Pubkey,
+        governing_token_mint: Pubkey,
+    ) -> Result<()> {
+        create_voter_weight_record::handler(ctx, realm, governing_token_mint)
+    }
+
+    pub fn update_voter_weight_record(ctx: Context<UpdateVoterWeightRecord>) -> Result<()> {
+        update_voter_weight_record::handler(ctx)
+    }
+
+    pub fn create_max_voter_weight_record(
+        ctx: Context<CreateMaxVoterWeightRecord>,
+        realm: Pubkey,
+        governing_token_mint: Pubkey,
+    ) -> Result<()> {
+        create_max_voter_weight_record::handler(ctx, realm, governing_token_mint)
+    }
+
+    pub fn update_max_voter_weight_record(ctx: Context<UpdateMaxVoterWeightRecord>) -> Result<()> {
+        update_max_voter_weight_record::handler(ctx)
+    }
 }
@@ -1,5 +1,14 @@
+use std::cmp;
+
 use anchor_lang::prelude::*;
 
+use crate::errors::SerumGovError;
+
+pub const MAX_CHECKPOINTS: usize = 64;
+pub const MAX_POSITION_ACTIONS: usize = 32;
+pub const MAX_CONFIG_CHANGES: usize = 64;
+pub const MAX_LOCK_TIERS: usize = 8;
+
 #[account]
 pub struct Config {
     pub config_authority: Pubkey,
@@ -9,10 +18,322 @@ pub struct Config {
     pub redeem_delay: i64,
     pub cliff_period: i64,
     pub linear_vesting_period: i64,
+    pub max_lockup_period: i64,
+    /// Voting weight multiplier for a lockup of `max_lockup_period`, in basis points.
+    pub max_lockup_multiplier: u64,
+    pub vest_weight_mode: VestWeightMode,
+    /// Voting weight of unvested gSRM when discounted, in basis points.
+    pub unvested_weight: u64,
+    /// NOTE: Pubkey::default() if burns aren't checked against SPL Governance.
+    pub governance_program: Pubkey,
+    pub realm: Pubkey,
+    pub governing_token_mint: Pubkey,
+    /// Positions younger than this have no voting weight.
+    pub min_position_age: i64,
+    pub weight_curve: WeightCurve,
+    /// Max voting weight of a single owner when capped.
+    pub max_owner_weight: u64,
+    /// Fixed-term lockups deposits can pick, by `lock_tier - 1`.
+    pub lock_tiers: [LockTier; MAX_LOCK_TIERS],
+    /// Highest multiplier any lock tier has had, which `update_lock_tiers` can only raise.
+    /// NOTE: Positions keep their multiplier, so this bounds them after a tier is lowered or removed.
+    pub max_lock_multiplier: u64,
 }
 
 impl Config {
-    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 8;
+    pub const LEN: usize = 8
+        + 32
+        + 32
+        + 32
+        + 8
+        + 8
+        + 8
+        + 8
+        + 8
+        + 8
+        + 1
+        + 8
+        + 32
+        + 32
+        + 32
+        + 8
+        + 1
+        + 8
+        + MAX_LOCK_TIERS * (8 + 8)
+        + 8;
+
+    /// Tier picked by a deposit, if `lock_tier` is set.
+    pub fn lock_tier(&self, lock_tier: u8) -> Option<LockTier> {
+        let index = usize::from(lock_tier).checked_sub(1)?;
+        self.lock_tiers
+            .get(index)
+            .copied()
+            .filter(|tier| tier.duration > 0)
+    }
+
+    /// CHECK: Delays and the cliff can't be negative, and vesting must take some time.
+    pub fn validate_params(
+        claim_delay: i64,
+        redeem_delay: i64,
+        cliff_period: i64,
+        linear_vesting_period: i64,
+    ) -> Result<()> {
+        if claim_delay < 0 || redeem_delay < 0 || cliff_period < 0 {
+            return err!(SerumGovError::NegativeDuration);
+        }
+
+        if linear_vesting_period <= 0 {
+            return err!(SerumGovError::ZeroVestingPeriod);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfigField {
+    ConfigAuthority,
+    ClaimDelay,
+    RedeemDelay,
+    CliffPeriod,
+    LinearVestingPeriod,
+    MaxLockupPeriod,
+    MaxLockupMultiplier,
+    VestWeightMode,
+    UnvestedWeight,
+    GovernanceProgram,
+    Realm,
+    GoverningTokenMint,
+    MinPositionAge,
+    WeightCurve,
+    MaxOwnerWeight,
+    LockTiers,
+    StatsSrmLocked,
+    StatsMsrmLocked,
+    StatsSrmVesting,
+    StatsMsrmVesting,
+    StatsGsrmUnclaimed,
+    StatsSrmRedeemable,
+    StatsMsrmRedeemable,
+    StatsUnderflows,
+}
+
+/// NOTE: Enum fields are stored as `U64` of their variant index.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfigValue {
+    Pubkey(Pubkey),
+    I64(i64),
+    U64(u64),
+    LockTier {
+        index: u8,
+        duration: i64,
+        multiplier: u64,
+    },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct ConfigChange {
+    pub field: ConfigField,
+    pub old_value: ConfigValue,
+    pub new_value: ConfigValue,
+    pub signer: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Recent changes made to Config, or to Stats totals, by the config authority.
+/// NOTE: Only changes made after the account was created are tracked.
+#[account]
+pub struct ConfigHistory {
+    pub bump: u8,
+    /// Changes are written in a ring, overwriting the oldest one after MAX_CONFIG_CHANGES.
+    pub change_count: u64,
+    pub changes: Vec<ConfigChange>,
+}
+
+impl ConfigHistory {
+    pub const LEN: usize = 8 + 1 + 8 + 4 + MAX_CONFIG_CHANGES * (1 + 33 + 33 + 32 + 8);
+
+    /// Records a change to `field`, unless its value stayed the same.
+    pub fn record(
+        &mut self,
+        field: ConfigField,
+        old_value: ConfigValue,
+        new_value: ConfigValue,
+        signer: Pubkey,
+        timestamp: i64,
+    ) -> Result<()> {
+        if old_value == new_value {
+            return Ok(());
+        }
+
+        let change = ConfigChange {
+            field,
+            old_value,
+            new_value,
+            signer,
+            timestamp,
+        };
+
+        let index = (self.change_count % MAX_CONFIG_CHANGES as u64) as usize;
+        if index < self.changes.len() {
+            self.changes[index] = change;
+        } else {
+            self.changes.push(change);
+        }
+        self.change_count = self
+            .change_count
+            .checked_add(1)
+            .ok_or(SerumGovError::MathOverflow)?;
+
+        Ok(())
+    }
+}
+
+/// Totals across all positions and tickets.
+/// NOTE: SRM and MSRM amounts are in their own native units, not gSRM.
+#[account]
+pub struct Stats {
+    pub bump: u8,
+    /// Backing unburned gSRM in LockedAccounts.
+    pub srm_locked: u64,
+    pub msrm_locked: u64,
+    /// Backing unburned gSRM in VestAccounts.
+    pub srm_vesting: u64,
+    pub msrm_vesting: u64,
+    /// Owed in ClaimTickets which haven't been claimed yet.
+    pub gsrm_unclaimed: u64,
+    /// Waiting in RedeemTickets which haven't been redeemed yet.
+    pub srm_redeemable: u64,
+    pub msrm_redeemable: u64,
+    /// Decreases which would have taken a total below zero, so the totals have drifted.
+    pub underflows: u64,
+}
+
+impl Stats {
+    pub const LEN: usize = 8 + 1 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8;
+
+    /// Sets up the account if it was just created with `init_if_needed`.
+    pub fn initialize(&mut self, bump: u8) {
+        self.bump = bump;
+    }
+
+    /// Takes `amount` off `total`, counting it in `underflows` if the total is too low.
+    /// NOTE: Saturating instead of failing, since Stats of existing deployments are seeded by hand,
+    /// and a bookkeeping error must never block exits.
+    fn saturating_sub(&mut self, total: u64, amount: u64) -> u64 {
+        match total.checked_sub(amount) {
+            Some(total) => total,
+            None => {
+                self.underflows = self.underflows.saturating_add(1);
+                0
+            }
+        }
+    }
+
+    /// Total backing the unburned gSRM of LockedAccounts or VestAccounts, in SRM or MSRM.
+    fn position_total(&mut self, is_vest: bool, is_msrm: bool) -> &mut u64 {
+        match (is_vest, is_msrm) {
+            (false, false) => &mut self.srm_locked,
+            (false, true) => &mut self.msrm_locked,
+            (true, false) => &mut self.srm_vesting,
+            (true, true) => &mut self.msrm_vesting,
+        }
+    }
+
+    /// Deposit or top-up of `amount` SRM or MSRM, owed as `gsrm_amount` in a ClaimTicket.
+    pub fn record_deposit(
+        &mut self,
+        is_vest: bool,
+        is_msrm: bool,
+        amount: u64,
+        gsrm_amount: u64,
+    ) -> Result<()> {
+        let total = self.position_total(is_vest, is_msrm);
+        *total = total
+            .checked_add(amount)
+            .ok_or(SerumGovError::MathOverflow)?;
+        self.gsrm_unclaimed = self
+            .gsrm_unclaimed
+            .checked_add(gsrm_amount)
+            .ok_or(SerumGovError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Claim of `gsrm_amount` from a ClaimTicket.
+    pub fn record_claim(&mut self, gsrm_amount: u64) {
+        self.gsrm_unclaimed = self.saturating_sub(self.gsrm_unclaimed, gsrm_amount);
+    }
+
+    /// Burn which moved `redeem_amount` SRM or MSRM from a position into a RedeemTicket.
+    pub fn record_burn(&mut self, is_vest: bool, is_msrm: bool, redeem_amount: u64) {
+        let total = *self.position_total(is_vest, is_msrm);
+        let total = self.saturating_sub(total, redeem_amount);
+        *self.position_total(is_vest, is_msrm) = total;
+
+        if is_msrm {
+            self.msrm_redeemable = self.msrm_redeemable.saturating_add(redeem_amount);
+        } else {
+            self.srm_redeemable = self.srm_redeemable.saturating_add(redeem_amount);
+        }
+    }
+
+    /// Redeem of `amount` SRM or MSRM from a RedeemTicket.
+    pub fn record_redeem(&mut self, is_msrm: bool, amount: u64) {
+        if is_msrm {
+            self.msrm_redeemable = self.saturating_sub(self.msrm_redeemable, amount);
+        } else {
+            self.srm_redeemable = self.saturating_sub(self.srm_redeemable, amount);
+        }
+    }
+
+    /// SRM the vault must hold to cover all positions and RedeemTickets.
+    pub fn srm_liabilities(&self) -> Result<u64> {
+        let liabilities = self
+            .srm_locked
+            .checked_add(self.srm_vesting)
+            .ok_or(SerumGovError::MathOverflow)?
+            .checked_add(self.srm_redeemable)
+            .ok_or(SerumGovError::MathOverflow)?;
+        Ok(liabilities)
+    }
+
+    /// MSRM the vault must hold to cover all positions and RedeemTickets.
+    pub fn msrm_liabilities(&self) -> Result<u64> {
+        let liabilities = self
+            .msrm_locked
+            .checked_add(self.msrm_vesting)
+            .ok_or(SerumGovError::MathOverflow)?
+            .checked_add(self.msrm_redeemable)
+            .ok_or(SerumGovError::MathOverflow)?;
+        Ok(liabilities)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VestWeightMode {
+    /// Unburned gSRM counts in full, vested or not.
+    Full,
+    /// Unvested gSRM counts at `unvested_weight`.
+    Discounted,
+}
+
+/// A fixed-term lockup.
+/// NOTE: Unused if `duration` is 0.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LockTier {
+    pub duration: i64,
+    /// Voting weight multiplier until the term ends, in basis points.
+    pub multiplier: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WeightCurve {
+    /// Voting weight of an owner is their voting power.
+    Linear,
+    /// Voting weight of an owner is the square root of their voting power, in whole gSRM.
+    SquareRoot,
+    /// Voting weight of an owner is their voting power, up to `max_owner_weight`.
+    Capped,
 }
 
 #[account]
@@ -21,12 +342,238 @@ pub struct User {
     pub bump: u8,
     pub lock_index: u64,
     pub vest_index: u64,
+    /// NOTE: Pubkey::default() if voting power isn't delegated.
+    pub delegate: Pubkey,
+    /// Running totals across the user's positions and tickets, same as in Stats.
+    /// NOTE: Only changes made after the account was created or migrated are tracked, so the
+    /// `record_*` methods saturate instead of failing.
+    pub srm_locked: u64,
+    pub msrm_locked: u64,
+    pub srm_vesting: u64,
+    pub msrm_vesting: u64,
+    pub gsrm_unclaimed: u64,
+    pub srm_redeemable: u64,
+    pub msrm_redeemable: u64,
+    pub gsrm_burned: u64,
+    pub open_claim_tickets: u64,
+    pub open_redeem_tickets: u64,
+    /// Positions closed with `close_position`.
+    /// NOTE: Every position was closed once this equals `lock_index + vest_index`.
+    pub closed_positions: u64,
 }
 
 impl User {
+    pub const LEN: usize = 8 + 32 + 1 + 8 + 8 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8;
+
+    /// Key which gets the voting power of this user's positions.
+    pub fn voter(&self) -> Pubkey {
+        if self.delegate == Pubkey::default() {
+            self.owner
+        } else {
+            self.delegate
+        }
+    }
+
+    /// Keys whose TokenOwnerRecords can hold votes cast with this user's positions.
+    pub fn voters(&self) -> Vec<Pubkey> {
+        if self.delegate == Pubkey::default() {
+            vec![self.owner]
+        } else {
+            vec![self.owner, self.delegate]
+        }
+    }
+
+    /// Total backing the unburned gSRM of LockedAccounts or VestAccounts, in SRM or MSRM.
+    fn position_total(&mut self, is_vest: bool, is_msrm: bool) -> &mut u64 {
+        match (is_vest, is_msrm) {
+            (false, false) => &mut self.srm_locked,
+            (false, true) => &mut self.msrm_locked,
+            (true, false) => &mut self.srm_vesting,
+            (true, true) => &mut self.msrm_vesting,
+        }
+    }
+
+    /// Deposit or top-up of `amount` SRM or MSRM, owed as `gsrm_amount` in a ClaimTicket.
+    pub fn record_deposit(&mut self, is_vest: bool, is_msrm: bool, amount: u64, gsrm_amount: u64) {
+        let total = self.position_total(is_vest, is_msrm);
+        *total = total.saturating_add(amount);
+        self.gsrm_unclaimed = self.gsrm_unclaimed.saturating_add(gsrm_amount);
+        self.open_claim_tickets = self.open_claim_tickets.saturating_add(1);
+    }
+
+    /// Claim of `gsrm_amount` from a ClaimTicket.
+    pub fn record_claim(&mut self, gsrm_amount: u64) {
+        self.gsrm_unclaimed = self.gsrm_unclaimed.saturating_sub(gsrm_amount);
+        self.open_claim_tickets = self.open_claim_tickets.saturating_sub(1);
+    }
+
+    /// Burn of `gsrm_amount` which moved `redeem_amount` SRM or MSRM from a position into a
+    /// RedeemTicket.
+    pub fn record_burn(
+        &mut self,
+        is_vest: bool,
+        is_msrm: bool,
+        redeem_amount: u64,
+        gsrm_amount: u64,
+    ) {
+        let total = self.position_total(is_vest, is_msrm);
+        *total = total.saturating_sub(redeem_amount);
+        if is_msrm {
+            self.msrm_redeemable = self.msrm_redeemable.saturating_add(redeem_amount);
+        } else {
+            self.srm_redeemable = self.srm_redeemable.saturating_add(redeem_amount);
+        }
+        self.gsrm_burned = self.gsrm_burned.saturating_add(gsrm_amount);
+        self.open_redeem_tickets = self.open_redeem_tickets.saturating_add(1);
+    }
+
+    /// Redeem of `amount` SRM or MSRM from a RedeemTicket.
+    pub fn record_redeem(&mut self, is_msrm: bool, amount: u64) {
+        if is_msrm {
+            self.msrm_redeemable = self.msrm_redeemable.saturating_sub(amount);
+        } else {
+            self.srm_redeemable = self.srm_redeemable.saturating_sub(amount);
+        }
+        self.open_redeem_tickets = self.open_redeem_tickets.saturating_sub(1);
+    }
+}
+
+/// Left behind by `close_user`, so a re-created User carries on from the same indexes
+/// instead of reusing the seeds of old positions.
+#[account]
+pub struct UserTombstone {
+    pub owner: Pubkey,
+    pub bump: u8,
+    pub lock_index: u64,
+    pub vest_index: u64,
+}
+
+impl UserTombstone {
     pub const LEN: usize = 8 + 32 + 1 + 8 + 8;
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct Checkpoint {
+    pub timestamp: i64,
+    pub unburned_gsrm: u64,
+}
+
+/// History of the unburned gSRM amount across all positions of a user.
+/// NOTE: This is the raw gSRM amount, not the voter weight, which also depends on lockups, lock tiers,
+/// delegation, position age and the weight curve. Only changes made after the account was created are tracked.
+#[account]
+pub struct Checkpoints {
+    pub owner: Pubkey,
+    pub bump: u8,
+    pub unburned_gsrm: u64,
+    /// Checkpoints are written in a ring, overwriting the oldest one after MAX_CHECKPOINTS.
+    pub checkpoint_count: u64,
+    pub checkpoints: Vec<Checkpoint>,
+    /// Whether the user already had positions when this account was created, which aren't accounted for.
+    pub untracked_positions: bool,
+    /// Paid the rent of this account, which is refunded to it when the User is closed.
+    pub rent_payer: Pubkey,
+}
+
+impl Checkpoints {
+    pub const LEN: usize = 8 + 32 + 1 + 8 + 8 + 4 + MAX_CHECKPOINTS * (8 + 8) + 1 + 32;
+
+    /// Sets up the account if it was just created with `init_if_needed`.
+    pub fn initialize(
+        &mut self,
+        owner: Pubkey,
+        bump: u8,
+        untracked_positions: bool,
+        rent_payer: Pubkey,
+    ) {
+        if self.owner == Pubkey::default() {
+            self.owner = owner;
+            self.bump = bump;
+            self.untracked_positions = untracked_positions;
+            self.rent_payer = rent_payer;
+        }
+    }
+
+    /// Sets up the account if it was just created with `init_if_needed` outside of a new deposit.
+    /// NOTE: The user must already have had positions then, which aren't accounted for in it.
+    pub fn initialize_untracked(&mut self, owner: Pubkey, bump: u8, rent_payer: Pubkey) {
+        self.initialize(owner, bump, true, rent_payer);
+    }
+
+    pub fn increase(&mut self, amount: u64, timestamp: i64) -> Result<()> {
+        let unburned_gsrm = self
+            .unburned_gsrm
+            .checked_add(amount)
+            .ok_or(SerumGovError::MathOverflow)?;
+        self.record(unburned_gsrm, timestamp)
+    }
+
+    pub fn decrease(&mut self, amount: u64, timestamp: i64) -> Result<()> {
+        // NOTE: Saturating since positions created before this account aren't accounted for.
+        self.record(self.unburned_gsrm.saturating_sub(amount), timestamp)
+    }
+
+    pub fn record(&mut self, unburned_gsrm: u64, timestamp: i64) -> Result<()> {
+        self.unburned_gsrm = unburned_gsrm;
+        let checkpoint = Checkpoint {
+            timestamp,
+            unburned_gsrm,
+        };
+
+        // Multiple changes at the same timestamp only keep the last one.
+        if self.checkpoint_count > 0 {
+            let latest = ((self.checkpoint_count - 1) % MAX_CHECKPOINTS as u64) as usize;
+            if self.checkpoints[latest].timestamp == timestamp {
+                self.checkpoints[latest] = checkpoint;
+                return Ok(());
+            }
+        }
+
+        let index = (self.checkpoint_count % MAX_CHECKPOINTS as u64) as usize;
+        if index < self.checkpoints.len() {
+            self.checkpoints[index] = checkpoint;
+        } else {
+            self.checkpoints.push(checkpoint);
+        }
+        self.checkpoint_count = self
+            .checkpoint_count
+            .checked_add(1)
+            .ok_or(SerumGovError::MathOverflow)?;
+
+        Ok(())
+    }
+
+    /// Returns None if `timestamp` is older than the oldest checkpoint still stored, or than the
+    /// first checkpoint if the amount before it isn't known.
+    pub fn unburned_gsrm_at(&self, timestamp: i64) -> Option<u64> {
+        let stored = self.checkpoints.len() as u64;
+
+        for i in 0..stored {
+            let index = ((self.checkpoint_count - 1 - i) % MAX_CHECKPOINTS as u64) as usize;
+            if self.checkpoints[index].timestamp <= timestamp {
+                return Some(self.checkpoints[index].unburned_gsrm);
+            }
+        }
+
+        // Before the first checkpoint the user had nothing, unless they had untracked positions.
+        if self.checkpoint_count == stored && !self.untracked_positions {
+            Some(0)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PositionStatus {
+    /// Has gSRM left to burn.
+    Active,
+    /// All gSRM was burned, the position can be closed once its tickets are settled.
+    FullyBurned,
+    /// Set by `close_position` right before the account is closed.
+    Closed,
+}
+
 #[account]
 pub struct LockedAccount {
     pub owner: Pubkey,
@@ -37,10 +584,24 @@ pub struct LockedAccount {
     pub created_at: i64,
     pub total_gsrm_amount: u64,
     pub gsrm_burned: u64,
+    /// NOTE: 0 if the tokens were locked without a lockup.
+    pub lockup_end: i64,
+    pub status: PositionStatus,
+    /// Paid the rent for this account, and gets it back when it's closed.
+    /// NOTE: Default for accounts created before this was tracked, which refund to the owner.
+    pub rent_payer: Pubkey,
+    /// NOTE: 0 if the tokens weren't locked for one of the Config's lock tiers.
+    pub lock_tier: u8,
+    /// Multiplier of the lock tier at the time of the deposit, in basis points.
+    pub lock_multiplier: u64,
 }
 
 impl LockedAccount {
-    pub const LEN: usize = 8 + 32 + 1 + 8 + 8 + 1 + 8 + 8 + 8;
+    pub const LEN: usize = 8 + 32 + 1 + 8 + 8 + 1 + 8 + 8 + 8 + 8 + 1 + 32 + 1 + 8;
+
+    pub fn refund_to(&self) -> Pubkey {
+        refund_to(self.rent_payer, self.owner)
+    }
 }
 
 #[account]
@@ -55,10 +616,136 @@ pub struct VestAccount {
     pub linear_vesting_period: i64,
     pub total_gsrm_amount: u64,
     pub gsrm_burned: u64,
+    pub status: PositionStatus,
+    /// Paid the rent for this account, and gets it back when it's closed.
+    /// NOTE: Default for accounts created before this was tracked, which refund to the owner.
+    pub rent_payer: Pubkey,
 }
 
 impl VestAccount {
-    pub const LEN: usize = 8 + 32 + 1 + 8 + 8 + 1 + 8 + 8 + 8 + 8 + 8;
+    pub const LEN: usize = 8 + 32 + 1 + 8 + 8 + 1 + 8 + 8 + 8 + 8 + 8 + 1 + 32;
+
+    pub fn refund_to(&self) -> Pubkey {
+        refund_to(self.rent_payer, self.owner)
+    }
+
+    /// gSRM vested by `now`, linearly over `linear_vesting_period` once the cliff has passed.
+    pub fn vested_amount(&self, now: i64) -> Result<u64> {
+        let cliff_end = self
+            .created_at
+            .checked_add(self.cliff_period)
+            .ok_or(SerumGovError::MathOverflow)?;
+
+        if now < cliff_end {
+            return Ok(0);
+        }
+
+        if self.linear_vesting_period <= 0 {
+            return Ok(self.total_gsrm_amount);
+        }
+
+        // vested_time = time passed after cliff period ended
+        let vested_time = now
+            .checked_sub(cliff_end)
+            .ok_or(SerumGovError::MathUnderflow)?;
+
+        // vested_amount = (vested_time / linear_vest_period) * total_gsrm_amount
+        let vested_amount = u128::from(self.total_gsrm_amount)
+            .checked_mul(
+                u128::try_from(vested_time).map_err(|_| SerumGovError::NegativeElapsedTime)?,
+            )
+            .ok_or(SerumGovError::MathOverflow)?
+            .checked_div(
+                u128::try_from(self.linear_vesting_period)
+                    .map_err(|_| SerumGovError::ZeroVestingPeriod)?,
+            )
+            .ok_or(SerumGovError::MathOverflow)?;
+
+        // If vested_time > linear_vest_period, vested_amount will be greater than total_gsrm_amount.
+        // Hence, vested_amount = min(vested_amount, total_gsrm_amount)
+        Ok(cmp::min(
+            u64::try_from(vested_amount).unwrap_or(u64::MAX),
+            self.total_gsrm_amount,
+        ))
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PositionActionKind {
+    /// `amount` is the gSRM issued through the ClaimTicket.
+    Deposit,
+    /// `amount` is the gSRM minted.
+    Claim,
+    /// `amount` is the gSRM burned for the RedeemTicket.
+    Burn,
+    /// `amount` is the SRM or MSRM sent back to the owner.
+    Redeem,
+    /// `amount` is the unburned gSRM merged in from the LockedAccount in `ticket`.
+    Merge,
+    /// `amount` is the unburned gSRM split between this LockedAccount and the one in `ticket`.
+    Split,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct PositionAction {
+    pub kind: PositionActionKind,
+    pub amount: u64,
+    pub timestamp: i64,
+    /// ClaimTicket or RedeemTicket the action was taken with.
+    /// NOTE: The other LockedAccount for `Merge` and `Split`.
+    pub ticket: Pubkey,
+}
+
+/// Recent actions taken on a LockedAccount or VestAccount.
+/// NOTE: Only actions taken after the account was created are tracked, and it's kept around after
+/// the position is closed.
+#[account]
+pub struct PositionHistory {
+    pub position: Pubkey,
+    pub bump: u8,
+    /// Actions are written in a ring, overwriting the oldest one after MAX_POSITION_ACTIONS.
+    pub action_count: u64,
+    pub actions: Vec<PositionAction>,
+}
+
+impl PositionHistory {
+    pub const LEN: usize = 8 + 32 + 1 + 8 + 4 + MAX_POSITION_ACTIONS * (1 + 8 + 8 + 32);
+
+    /// Sets up the account if it was just created with `init_if_needed`.
+    pub fn initialize(&mut self, position: Pubkey, bump: u8) {
+        if self.position == Pubkey::default() {
+            self.position = position;
+            self.bump = bump;
+        }
+    }
+
+    pub fn record(
+        &mut self,
+        kind: PositionActionKind,
+        amount: u64,
+        ticket: Pubkey,
+        timestamp: i64,
+    ) -> Result<()> {
+        let action = PositionAction {
+            kind,
+            amount,
+            timestamp,
+            ticket,
+        };
+
+        let index = (self.action_count % MAX_POSITION_ACTIONS as u64) as usize;
+        if index < self.actions.len() {
+            self.actions[index] = action;
+        } else {
+            self.actions.push(action);
+        }
+        self.action_count = self
+            .action_count
+            .checked_add(1)
+            .ok_or(SerumGovError::MathOverflow)?;
+
+        Ok(())
+    }
 }
 
 #[account]
@@ -69,10 +756,26 @@ pub struct ClaimTicket {
     pub created_at: i64,
     pub claim_delay: i64,
     pub gsrm_amount: u64,
+    /// Paid the rent for this account, and gets it back when it's claimed.
+    /// NOTE: Default for tickets created before this was tracked, which refund to the owner.
+    pub rent_payer: Pubkey,
 }
 
 impl ClaimTicket {
-    pub const LEN: usize = 8 + 32 + 32 + 1 + 8 + 8 + 8;
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 8 + 8 + 8 + 32;
+
+    pub fn refund_to(&self) -> Pubkey {
+        refund_to(self.rent_payer, self.owner)
+    }
+}
+
+/// Account which gets the rent back when an account paid for by `rent_payer` is closed.
+fn refund_to(rent_payer: Pubkey, owner: Pubkey) -> Pubkey {
+    if rent_payer == Pubkey::default() {
+        owner
+    } else {
+        rent_payer
+    }
 }
 
 #[account]
@@ -90,3 +793,45 @@ pub struct RedeemTicket {
 impl RedeemTicket {
     pub const LEN: usize = 8 + 32 + 32 + 8 + 1 + 1 + 8 + 8 + 8;
 }
+
+/// Voter weight add-in record read by SPL Governance.
+/// NOTE: Layout (and discriminator) must match `spl-governance-addin-api`.
+#[account]
+pub struct VoterWeightRecord {
+    pub realm: Pubkey,
+    pub governing_token_mint: Pubkey,
+    pub governing_token_owner: Pubkey,
+    pub voter_weight: u64,
+    pub voter_weight_expiry: Option<u64>,
+    pub weight_action: Option<VoterWeightAction>,
+    pub weight_action_target: Option<Pubkey>,
+    pub reserved: [u8; 8],
+}
+
+impl VoterWeightRecord {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + (1 + 8) + (1 + 1) + (1 + 32) + 8;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VoterWeightAction {
+    CastVote,
+    CommentProposal,
+    CreateGovernance,
+    CreateProposal,
+    SignOffProposal,
+}
+
+/// Max voter weight add-in record read by SPL Governance.
+/// NOTE: Layout (and discriminator) must match `spl-governance-addin-api`.
+#[account]
+pub struct MaxVoterWeightRecord {
+    pub realm: Pubkey,
+    pub governing_token_mint: Pubkey,
+    pub max_voter_weight: u64,
+    pub max_voter_weight_expiry: Option<u64>,
+    pub reserved: [u8; 8],
+}
+
+impl MaxVoterWeightRecord {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + (1 + 8) + 8;
+}
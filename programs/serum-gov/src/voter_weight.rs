@@ -0,0 +1,404 @@
+use std::cmp;
+
+use anchor_lang::{prelude::*, Discriminator};
+
+use crate::errors::SerumGovError;
+use crate::state::{
+    Config, LockedAccount, PositionStatus, User, VestAccount, VestWeightMode, WeightCurve,
+};
+use crate::{BASIS_POINTS, GSRM_UNIT, MSRM_MULTIPLIER};
+
+/// A LockedAccount or VestAccount passed in through `remaining_accounts`.
+pub enum Position {
+    Locked(LockedAccount),
+    Vest(VestAccount),
+}
+
+impl Position {
+    pub fn load(account: &AccountInfo) -> Result<Self> {
+        if account.owner != &crate::ID {
+            return err!(SerumGovError::InvalidPositionAccount);
+        }
+
+        let data = account.try_borrow_data()?;
+        if data.len() < 8 {
+            return err!(SerumGovError::InvalidPositionAccount);
+        }
+
+        let discriminator: [u8; 8] = data[..8]
+            .try_into()
+            .map_err(|_| SerumGovError::InvalidPositionAccount)?;
+        let mut data: &[u8] = &data;
+
+        if discriminator == LockedAccount::discriminator() {
+            Ok(Position::Locked(LockedAccount::try_deserialize(&mut data)?))
+        } else if discriminator == VestAccount::discriminator() {
+            Ok(Position::Vest(VestAccount::try_deserialize(&mut data)?))
+        } else {
+            err!(SerumGovError::InvalidPositionAccount)
+        }
+    }
+
+    pub fn owner(&self) -> Pubkey {
+        match self {
+            Position::Locked(locked_account) => locked_account.owner,
+            Position::Vest(vest_account) => vest_account.owner,
+        }
+    }
+
+    pub fn refund_to(&self) -> Pubkey {
+        match self {
+            Position::Locked(locked_account) => locked_account.refund_to(),
+            Position::Vest(vest_account) => vest_account.refund_to(),
+        }
+    }
+
+    pub fn redeem_index(&self) -> u64 {
+        match self {
+            Position::Locked(locked_account) => locked_account.redeem_index,
+            Position::Vest(vest_account) => vest_account.redeem_index,
+        }
+    }
+
+    pub fn status(&self) -> PositionStatus {
+        match self {
+            Position::Locked(locked_account) => locked_account.status,
+            Position::Vest(vest_account) => vest_account.status,
+        }
+    }
+
+    pub fn created_at(&self) -> i64 {
+        match self {
+            Position::Locked(locked_account) => locked_account.created_at,
+            Position::Vest(vest_account) => vest_account.created_at,
+        }
+    }
+
+    /// gSRM backed by this position which hasn't been burned yet, scaled by the lockup multiplier
+    /// for LockedAccounts, and with unvested gSRM discounted for VestAccounts if configured.
+    /// NOTE: 0 until the position is `min_position_age` old.
+    pub fn voting_power(&self, config: &Config, now: i64) -> Result<u64> {
+        // Positions need to age before counting, so they can't be opened just to swing a vote.
+        if now.saturating_sub(self.created_at()) < config.min_position_age {
+            return Ok(0);
+        }
+
+        let voting_power = match self {
+            Position::Locked(locked_account) => {
+                let amount = locked_account
+                    .total_gsrm_amount
+                    .checked_sub(locked_account.gsrm_burned)
+                    .ok_or(SerumGovError::MathUnderflow)?;
+                let bonus = if locked_account.lock_tier > 0 {
+                    lock_tier_bonus(
+                        amount,
+                        locked_account.lock_multiplier,
+                        locked_account.lockup_end,
+                        now,
+                    )?
+                } else {
+                    lockup_bonus(config, amount, locked_account.lockup_end, now)?
+                };
+                amount
+                    .checked_add(bonus)
+                    .ok_or(SerumGovError::MathOverflow)?
+            }
+            Position::Vest(vest_account) => match config.vest_weight_mode {
+                VestWeightMode::Full => vest_account
+                    .total_gsrm_amount
+                    .checked_sub(vest_account.gsrm_burned)
+                    .ok_or(SerumGovError::MathUnderflow)?,
+                VestWeightMode::Discounted => {
+                    let vested_amount = vest_account.vested_amount(now)?;
+                    let unvested_amount = vest_account
+                        .total_gsrm_amount
+                        .checked_sub(vested_amount)
+                        .ok_or(SerumGovError::MathUnderflow)?;
+
+                    // NOTE: Only vested gSRM can be burned, so the burned amount is never unvested.
+                    let unvested_weight = u128::from(unvested_amount)
+                        .checked_mul(u128::from(config.unvested_weight))
+                        .ok_or(SerumGovError::MathOverflow)?
+                        .checked_div(u128::from(BASIS_POINTS))
+                        .ok_or(SerumGovError::MathOverflow)?;
+
+                    vested_amount
+                        .saturating_sub(vest_account.gsrm_burned)
+                        .checked_add(
+                            u64::try_from(unvested_weight)
+                                .map_err(|_| SerumGovError::AmountTooLarge)?,
+                        )
+                        .ok_or(SerumGovError::MathOverflow)?
+                }
+            },
+        };
+
+        Ok(voting_power)
+    }
+}
+
+/// Extra voting weight for locking `amount` until `lockup_end`.
+/// It decays linearly from `max_lockup_multiplier` to nothing as the lockup approaches its end.
+fn lockup_bonus(config: &Config, amount: u64, lockup_end: i64, now: i64) -> Result<u64> {
+    if lockup_end <= now
+        || config.max_lockup_period <= 0
+        || config.max_lockup_multiplier <= BASIS_POINTS
+    {
+        return Ok(0);
+    }
+
+    let remaining = cmp::min(lockup_end - now, config.max_lockup_period);
+
+    // bonus = amount * (max_lockup_multiplier - 1) * (remaining / max_lockup_period)
+    let bonus = u128::from(amount)
+        .checked_mul(u128::from(config.max_lockup_multiplier - BASIS_POINTS))
+        .ok_or(SerumGovError::MathOverflow)?
+        .checked_mul(u128::try_from(remaining).map_err(|_| SerumGovError::NegativeDuration)?)
+        .ok_or(SerumGovError::MathOverflow)?
+        .checked_div(
+            u128::from(BASIS_POINTS)
+                .checked_mul(
+                    u128::try_from(config.max_lockup_period)
+                        .map_err(|_| SerumGovError::NegativeDuration)?,
+                )
+                .ok_or(SerumGovError::MathOverflow)?,
+        )
+        .ok_or(SerumGovError::MathOverflow)?;
+
+    let bonus = u64::try_from(bonus).map_err(|_| SerumGovError::AmountTooLarge)?;
+    Ok(bonus)
+}
+
+/// Voting weight of an owner with `voting_power` across their positions, as per `weight_curve`.
+/// NOTE: Never more than `voting_power`, so the max voter weight still holds.
+fn owner_weight(config: &Config, voting_power: u64) -> Result<u64> {
+    let weight = match config.weight_curve {
+        WeightCurve::Linear => voting_power,
+        WeightCurve::SquareRoot => {
+            // sqrt is taken in whole gSRM, so 1 gSRM still has a weight of 1 gSRM.
+            let weight = isqrt(
+                u128::from(voting_power)
+                    .checked_mul(u128::from(GSRM_UNIT))
+                    .ok_or(SerumGovError::MathOverflow)?,
+            );
+            cmp::min(
+                voting_power,
+                u64::try_from(weight).map_err(|_| SerumGovError::AmountTooLarge)?,
+            )
+        }
+        WeightCurve::Capped => cmp::min(voting_power, config.max_owner_weight),
+    };
+
+    Ok(weight)
+}
+
+/// Integer square root, rounded down.
+fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+
+    // Newton's method, starting at or above the root so it decreases monotonically.
+    let mut x = n;
+    let mut y = n / 2;
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+
+    x
+}
+
+/// Extra voting weight for locking `amount` for a lock tier with `lock_multiplier`.
+/// Unlike `lockup_bonus`, it doesn't decay until the term ends.
+fn lock_tier_bonus(amount: u64, lock_multiplier: u64, lockup_end: i64, now: i64) -> Result<u64> {
+    if lockup_end <= now || lock_multiplier <= BASIS_POINTS {
+        return Ok(0);
+    }
+
+    // bonus = amount * (lock_multiplier - 1)
+    let bonus = u128::from(amount)
+        .checked_mul(u128::from(lock_multiplier - BASIS_POINTS))
+        .ok_or(SerumGovError::MathOverflow)?
+        .checked_div(u128::from(BASIS_POINTS))
+        .ok_or(SerumGovError::MathOverflow)?;
+
+    let bonus = u64::try_from(bonus).map_err(|_| SerumGovError::AmountTooLarge)?;
+    Ok(bonus)
+}
+
+/// Lockup end and multiplier of a deposit for `lock_tier`, or for `lockup_end` if no tier is picked.
+/// NOTE: Lock tiers set their own lockup end, so `lockup_end` must be 0 with one.
+pub fn lock_terms(config: &Config, lock_tier: u8, lockup_end: i64, now: i64) -> Result<(i64, u64)> {
+    if lock_tier == 0 {
+        validate_lockup_end(config, lockup_end, now)?;
+        return Ok((lockup_end, 0));
+    }
+
+    if lockup_end != 0 {
+        return err!(SerumGovError::InvalidLockupEnd);
+    }
+
+    let tier = match config.lock_tier(lock_tier) {
+        Some(tier) => tier,
+        None => return err!(SerumGovError::InvalidLockTier),
+    };
+
+    let lockup_end = now
+        .checked_add(tier.duration)
+        .ok_or(SerumGovError::MathOverflow)?;
+
+    Ok((lockup_end, tier.multiplier))
+}
+
+/// Lockup end and multiplier of `locked_account` once topped up.
+/// NOTE: Positions of a lock tier are locked for its full term again, as a new deposit would be,
+/// since the tier's multiplier doesn't decay until the lockup ends.
+pub fn top_up_terms(
+    config: &Config,
+    locked_account: &LockedAccount,
+    now: i64,
+) -> Result<(i64, u64)> {
+    if locked_account.lock_tier == 0 {
+        return Ok((locked_account.lockup_end, locked_account.lock_multiplier));
+    }
+
+    let (lockup_end, lock_multiplier) = lock_terms(config, locked_account.lock_tier, 0, now)?;
+    Ok((
+        cmp::max(locked_account.lockup_end, lockup_end),
+        lock_multiplier,
+    ))
+}
+
+/// Longest `locked_account` can be locked up for from now.
+/// NOTE: Lock tiers can be longer than `max_lockup_period`, in which case positions locked for
+/// one can be locked up for as long as the tier's term.
+pub fn max_lock_duration(config: &Config, locked_account: &LockedAccount) -> i64 {
+    match config.lock_tier(locked_account.lock_tier) {
+        Some(tier) => cmp::max(tier.duration, config.max_lockup_period),
+        None => config.max_lockup_period,
+    }
+}
+
+/// CHECK: Lockups must end in the future, but not further than `max_lockup_period` from now.
+pub fn validate_lockup_end(config: &Config, lockup_end: i64, now: i64) -> Result<()> {
+    // No lockup.
+    if lockup_end == 0 {
+        return Ok(());
+    }
+
+    if lockup_end <= now || lockup_end - now > config.max_lockup_period {
+        return err!(SerumGovError::InvalidLockupEnd);
+    }
+
+    Ok(())
+}
+
+/// Loads `account` if it's a User account.
+fn load_user(account: &AccountInfo) -> Result<Option<User>> {
+    if account.owner != &crate::ID {
+        return Ok(None);
+    }
+
+    let data = account.try_borrow_data()?;
+    if data.len() < 8 || data[..8] != User::discriminator() {
+        return Ok(None);
+    }
+
+    let mut data: &[u8] = &data;
+    Ok(Some(User::try_deserialize(&mut data)?))
+}
+
+/// Sums up the voting weight of all positions in `accounts` controlled by `voter`.
+/// NOTE: Positions must be preceded by the User account of their owner, which decides
+/// whether they count for the owner or for the owner's delegate.
+/// The weight curve is applied to the total voting power of each owner.
+pub fn voter_weight_from_positions(
+    accounts: &[AccountInfo],
+    voter: &Pubkey,
+    config: &Config,
+    now: i64,
+) -> Result<u64> {
+    let mut seen: Vec<Pubkey> = Vec::with_capacity(accounts.len());
+    // Voting power of each owner passed in.
+    let mut owners: Vec<(Pubkey, u64)> = Vec::new();
+
+    for account in accounts {
+        // CHECK: Same account can't be counted twice.
+        if seen.contains(account.key) {
+            return err!(SerumGovError::DuplicatePositionAccount);
+        }
+        seen.push(account.key());
+
+        if let Some(user) = load_user(account)? {
+            // CHECK: User must be the voter, or must have delegated to the voter.
+            if user.voter() != *voter {
+                return err!(SerumGovError::InvalidDelegate);
+            }
+            owners.push((user.owner, 0));
+            continue;
+        }
+
+        let position = Position::load(account)?;
+
+        // CHECK: Only positions of the users passed in above count towards the weight.
+        let voting_power = match owners
+            .iter_mut()
+            .find(|(owner, _)| *owner == position.owner())
+        {
+            Some((_, voting_power)) => voting_power,
+            None => return err!(SerumGovError::InvalidPositionOwner),
+        };
+
+        *voting_power = voting_power
+            .checked_add(position.voting_power(config, now)?)
+            .ok_or(SerumGovError::MathOverflow)?;
+    }
+
+    let mut voter_weight: u64 = 0;
+    for (_, voting_power) in owners {
+        voter_weight = voter_weight
+            .checked_add(owner_weight(config, voting_power)?)
+            .ok_or(SerumGovError::MathOverflow)?;
+    }
+
+    Ok(voter_weight)
+}
+
+/// Upper bound for voter weight, given the SRM and MSRM backing gSRM.
+/// NOTE: Assumes all of it is locked for `max_lockup_period`, or for the highest multiplier
+/// any lock tier has had, since positions keep the multiplier they were locked with.
+/// CHECK: Other weight curves shrink the weight of each owner depending on how gSRM is spread
+/// across owners, so no max would be reachable under them.
+pub fn max_voter_weight(config: &Config, srm_amount: u64, msrm_amount: u64) -> Result<u64> {
+    if config.weight_curve != WeightCurve::Linear {
+        return err!(SerumGovError::NonLinearWeightCurve);
+    }
+
+    let amount = msrm_amount
+        .checked_mul(MSRM_MULTIPLIER)
+        .ok_or(SerumGovError::MathOverflow)?
+        .checked_add(srm_amount)
+        .ok_or(SerumGovError::MathOverflow)?;
+
+    let multiplier = config
+        .lock_tiers
+        .iter()
+        .filter(|tier| tier.duration > 0)
+        .map(|tier| tier.multiplier)
+        .fold(
+            cmp::max(config.max_lockup_multiplier, BASIS_POINTS),
+            cmp::max,
+        )
+        .max(config.max_lock_multiplier);
+
+    let max_voter_weight = u128::from(amount)
+        .checked_mul(u128::from(multiplier))
+        .ok_or(SerumGovError::MathOverflow)?
+        .checked_div(u128::from(BASIS_POINTS))
+        .ok_or(SerumGovError::MathOverflow)?;
+
+    let max_voter_weight =
+        u64::try_from(max_voter_weight).map_err(|_| SerumGovError::AmountTooLarge)?;
+    Ok(max_voter_weight)
+}
@@ -7,6 +7,7 @@ import {
   SystemProgram,
   SYSVAR_CLOCK_PUBKEY,
   SYSVAR_RENT_PUBKEY,
+  Transaction,
 } from "@solana/web3.js";
 import {
   createAccount,
@@ -15,6 +16,7 @@ import {
   getMint,
   mintTo,
   TOKEN_PROGRAM_ID,
+  transfer,
 } from "@solana/spl-token";
 import { Metaplex, TokenMetadataProgram } from "@metaplex-foundation/js";
 import { SerumGov } from "../target/types/serum_gov";
@@ -49,6 +51,19 @@ describe("serum-gov", () => {
 
   const alice = Keypair.generate();
 
+  // Any realm works for the voter weight add-in, records are keyed by it.
+  // NOTE: Fixed, since tests/fixtures has a TokenOwnerRecord in this realm.
+  const realm = new PublicKey("4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi");
+  const governanceProgram = new PublicKey(
+    "GovER5Lthms3bLBqWub97yVrMmEogzX7xNjdXpPPCVZw"
+  );
+
+  // Has an unrelinquished vote in the realm, see tests/fixtures/carol_token_owner_record.json.
+  const carol = Keypair.fromSeed(new Uint8Array(32).fill(7));
+
+  // Has a User from before `closed_positions`, see tests/fixtures/dave_legacy_user.json.
+  const dave = Keypair.fromSeed(new Uint8Array(32).fill(8));
+
   let sbfSrmAccount: PublicKey;
   let sbfMsrmAccount: PublicKey;
 
@@ -64,6 +79,14 @@ describe("serum-gov", () => {
     [Buffer.from("config")],
     program.programId
   );
+  const [stats] = findProgramAddressSync(
+    [Buffer.from("stats")],
+    program.programId
+  );
+  const [configHistory] = findProgramAddressSync(
+    [Buffer.from("config_history")],
+    program.programId
+  );
   const [GSRM_MINT] = findProgramAddressSync(
     [Buffer.from("gSRM")],
     program.programId
@@ -74,6 +97,23 @@ describe("serum-gov", () => {
     program.programId
   );
 
+  const userTombstone = (owner: PublicKey) =>
+    findProgramAddressSync(
+      [Buffer.from("user_tombstone"), owner.toBuffer()],
+      program.programId
+    )[0];
+
+  const checkpointsOf = (owner: PublicKey) =>
+    findProgramAddressSync(
+      [Buffer.from("checkpoints"), owner.toBuffer()],
+      program.programId
+    )[0];
+
+  const [aliceCheckpoints] = findProgramAddressSync(
+    [Buffer.from("checkpoints"), alice.publicKey.toBuffer()],
+    program.programId
+  );
+
   const [gsrmMetadata] = findProgramAddressSync(
     [
       Buffer.from("metadata"),
@@ -83,6 +123,89 @@ describe("serum-gov", () => {
     TokenMetadataProgram.publicKey
   );
 
+  const tokenOwnerRecord = (owner: PublicKey) =>
+    findProgramAddressSync(
+      [
+        Buffer.from("governance"),
+        realm.toBuffer(),
+        GSRM_MINT.toBuffer(),
+        owner.toBuffer(),
+      ],
+      governanceProgram
+    )[0];
+
+  const claimTicketOf = (position: PublicKey) =>
+    findProgramAddressSync(
+      [Buffer.from("claim_ticket"), position.toBuffer()],
+      program.programId
+    )[0];
+
+  const positionHistory = (position: PublicKey) =>
+    findProgramAddressSync(
+      [Buffer.from("position_history"), position.toBuffer()],
+      program.programId
+    )[0];
+
+  // User account of alice followed by all of her positions, for voter weight instructions.
+  const aliceVoterAccounts = async () => {
+    const positions = [
+      ...(await program.account.lockedAccount.all([
+        {
+          memcmp: {
+            offset: 8,
+            bytes: alice.publicKey.toBase58(),
+          },
+        },
+      ])),
+      ...(await program.account.vestAccount.all([
+        {
+          memcmp: {
+            offset: 8,
+            bytes: alice.publicKey.toBase58(),
+          },
+        },
+      ])),
+    ];
+
+    return [aliceUserAccount, ...positions.map((p) => p.publicKey)].map(
+      (pubkey) => ({ pubkey, isWritable: false, isSigner: false })
+    );
+  };
+
+  // Voter weight of alice with only `positions` passed in.
+  const aliceWeightOf = async (positions: PublicKey[]) => {
+    const [voterWeightRecord] = findProgramAddressSync(
+      [
+        Buffer.from("voter_weight_record"),
+        realm.toBuffer(),
+        GSRM_MINT.toBuffer(),
+        alice.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+
+    await program.methods
+      .updateVoterWeightRecord()
+      .accounts({
+        config,
+        voterWeightRecord,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      })
+      .remainingAccounts(
+        [aliceUserAccount, ...positions].map((pubkey) => ({
+          pubkey,
+          isWritable: false,
+          isSigner: false,
+        }))
+      )
+      .rpc();
+
+    const record = await program.account.voterWeightRecord.fetch(
+      voterWeightRecord
+    );
+    return record.voterWeight;
+  };
+
   before(async () => {
     // Airdrop sbf
     const sbfDrop = await connection.requestAirdrop(
@@ -214,6 +337,7 @@ describe("serum-gov", () => {
         payer: sbf.publicKey,
         authority,
         config,
+        stats,
         gsrmMint: GSRM_MINT,
         gsrmMetadata,
         srmMint: SRM_MINT,
@@ -270,6 +394,8 @@ describe("serum-gov", () => {
       .accounts({
         config,
         configAuthority: sbf.publicKey,
+        configHistory,
+        systemProgram: SystemProgram.programId,
       })
       .signers([sbf])
       .rpc();
@@ -287,6 +413,8 @@ describe("serum-gov", () => {
         .accounts({
           config,
           configAuthority: sbf.publicKey,
+          configHistory,
+          systemProgram: SystemProgram.programId,
         })
         .signers([sbf])
         .rpc();
@@ -304,6 +432,8 @@ describe("serum-gov", () => {
         .accounts({
           config,
           configAuthority: sbf.publicKey,
+          configHistory,
+          systemProgram: SystemProgram.programId,
         })
         .signers([sbf])
         .rpc();
@@ -314,12 +444,34 @@ describe("serum-gov", () => {
     }
   });
 
+  it("cant update config params with zero vesting period", async () => {
+    try {
+      await program.methods
+        .updateConfigParams(new BN(2), new BN(2), new BN(12), new BN(0))
+        .accounts({
+          config,
+          configAuthority: alice.publicKey,
+          configHistory,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([alice])
+        .rpc();
+      assert(false);
+    } catch (e) {
+      if (e instanceof AnchorError) {
+        expect(e.error.errorCode.code).to.equal("ZeroVestingPeriod");
+      } else assert(false);
+    }
+  });
+
   it("can update config params", async () => {
     await program.methods
       .updateConfigParams(new BN(2), new BN(2), new BN(12), new BN(500))
       .accounts({
         config,
         configAuthority: alice.publicKey,
+        configHistory,
+        systemProgram: SystemProgram.programId,
       })
       .signers([alice])
       .rpc();
@@ -331,6 +483,24 @@ describe("serum-gov", () => {
     expect(configAccount.linearVestingPeriod.toNumber()).to.equal(500);
   });
 
+  it("records config history", async () => {
+    const history = await program.account.configHistory.fetch(configHistory);
+    const changes = history.changes.slice(0, history.changeCount.toNumber());
+
+    const cliffPeriodChange = changes.find(
+      (c) => c.field.cliffPeriod !== undefined
+    );
+    expect(cliffPeriodChange.oldValue.i64[0].toNumber()).to.equal(10);
+    expect(cliffPeriodChange.newValue.i64[0].toNumber()).to.equal(12);
+    expect(cliffPeriodChange.signer.toBase58()).to.equal(
+      alice.publicKey.toBase58()
+    );
+
+    // Unchanged values aren't recorded.
+    expect(changes.find((c) => c.field.claimDelay !== undefined)).to.be
+      .undefined;
+  });
+
   it("cant init twice", async () => {
     try {
       await program.methods
@@ -347,6 +517,7 @@ describe("serum-gov", () => {
           payer: sbf.publicKey,
           authority,
           config,
+          stats,
           gsrmMint: GSRM_MINT,
           gsrmMetadata,
           srmMint: SRM_MINT,
@@ -372,6 +543,7 @@ describe("serum-gov", () => {
       .accounts({
         payer: alice.publicKey,
         userAccount: aliceUserAccount,
+        userTombstone: userTombstone(alice.publicKey),
         systemProgram: SystemProgram.programId,
       })
       .signers([alice])
@@ -401,8 +573,10 @@ describe("serum-gov", () => {
     );
 
     await program.methods
-      .depositLockedSrm(new BN(200_000_000))
+      .depositLockedSrm(new BN(200_000_000), new BN(0), 0)
       .accounts({
+        ownerCheckpoints: aliceCheckpoints,
+        stats,
         payer: sbf.publicKey,
         owner: alice.publicKey,
         ownerUserAccount: aliceUserAccount,
@@ -412,6 +586,7 @@ describe("serum-gov", () => {
         config,
         srmVault,
         lockedAccount: aliceLockedAccount,
+        positionHistory: positionHistory(aliceLockedAccount),
         claimTicket: claimTicket,
         clock: SYSVAR_CLOCK_PUBKEY,
         tokenProgram: TOKEN_PROGRAM_ID,
@@ -450,8 +625,10 @@ describe("serum-gov", () => {
     );
 
     await program.methods
-      .depositLockedMsrm(new BN(1))
+      .depositLockedMsrm(new BN(1), new BN(0), 0)
       .accounts({
+        ownerCheckpoints: aliceCheckpoints,
+        stats,
         payer: alice.publicKey,
         owner: alice.publicKey,
         ownerUserAccount: aliceUserAccount,
@@ -461,6 +638,7 @@ describe("serum-gov", () => {
         config,
         msrmVault,
         lockedAccount: aliceLockedAccount,
+        positionHistory: positionHistory(aliceLockedAccount),
         claimTicket: claimTicket,
         clock: SYSVAR_CLOCK_PUBKEY,
         tokenProgram: TOKEN_PROGRAM_ID,
@@ -496,14 +674,23 @@ describe("serum-gov", () => {
         },
       },
     ]);
+    expect(
+      aliceClaimTickets.some((t) => t.account.rentPayer.equals(sbf.publicKey))
+    ).to.be.true;
+    const sbfLamports = await connection.getBalance(sbf.publicKey);
 
     await Promise.all(
       aliceClaimTickets.map((claimTicket) =>
         program.methods
           .claim()
           .accounts({
+            ownerCheckpoints: aliceCheckpoints,
+            stats,
+            ownerUserAccount: aliceUserAccount,
             owner: alice.publicKey,
             claimTicket: claimTicket.publicKey,
+            rentPayer: claimTicket.account.rentPayer,
+            positionHistory: positionHistory(claimTicket.account.depositAccount),
             authority,
             gsrmMint: GSRM_MINT,
             ownerGsrmAccount: aliceGSRMAccount.publicKey,
@@ -522,6 +709,11 @@ describe("serum-gov", () => {
 
     expect(aliceGsrmBalance.value.uiAmount).to.equal(1_000_000 + 200);
 
+    // sbf paid for one of the ClaimTickets, so gets its rent back.
+    expect(await connection.getBalance(sbf.publicKey)).to.be.greaterThan(
+      sbfLamports
+    );
+
     aliceClaimTickets = await program.account.claimTicket.all([
       {
         memcmp: {
@@ -556,12 +748,16 @@ describe("serum-gov", () => {
     await program.methods
       .burnLockedGsrm(new BN(100_000_000))
       .accounts({
+        ownerCheckpoints: aliceCheckpoints,
+        stats,
+        ownerUserAccount: aliceUserAccount,
         owner: alice.publicKey,
         authority,
         config,
         gsrmMint: GSRM_MINT,
         ownerGsrmAccount: aliceGSRMAccount.publicKey,
         lockedAccount: aliceLockedAccount,
+        positionHistory: positionHistory(aliceLockedAccount),
         redeemTicket: redeemTicket,
         clock: SYSVAR_CLOCK_PUBKEY,
         tokenProgram: TOKEN_PROGRAM_ID,
@@ -612,12 +808,16 @@ describe("serum-gov", () => {
       await program.methods
         .burnLockedGsrm(new BN(200_000_000))
         .accounts({
+          ownerCheckpoints: aliceCheckpoints,
+          stats,
+          ownerUserAccount: aliceUserAccount,
           owner: alice.publicKey,
           authority,
           config,
           gsrmMint: GSRM_MINT,
           ownerGsrmAccount: aliceGSRMAccount.publicKey,
           lockedAccount: aliceLockedAccount,
+          positionHistory: positionHistory(aliceLockedAccount),
           redeemTicket: redeemTicket,
           clock: SYSVAR_CLOCK_PUBKEY,
           tokenProgram: TOKEN_PROGRAM_ID,
@@ -656,12 +856,16 @@ describe("serum-gov", () => {
       await program.methods
         .burnLockedGsrm(new BN(MSRM_MULTIPLIER - 1_000))
         .accounts({
+          ownerCheckpoints: aliceCheckpoints,
+          stats,
+          ownerUserAccount: aliceUserAccount,
           owner: alice.publicKey,
           authority,
           config,
           gsrmMint: GSRM_MINT,
           ownerGsrmAccount: aliceGSRMAccount.publicKey,
           lockedAccount: aliceLockedAccount,
+          positionHistory: positionHistory(aliceLockedAccount),
           redeemTicket: redeemTicket,
           clock: SYSVAR_CLOCK_PUBKEY,
           tokenProgram: TOKEN_PROGRAM_ID,
@@ -699,12 +903,16 @@ describe("serum-gov", () => {
     await program.methods
       .burnLockedGsrm(new BN(1 * MSRM_MULTIPLIER))
       .accounts({
+        ownerCheckpoints: aliceCheckpoints,
+        stats,
+        ownerUserAccount: aliceUserAccount,
         owner: alice.publicKey,
         authority,
         config,
         gsrmMint: GSRM_MINT,
         ownerGsrmAccount: aliceGSRMAccount.publicKey,
         lockedAccount: aliceLockedAccount,
+        positionHistory: positionHistory(aliceLockedAccount),
         redeemTicket: redeemTicket,
         clock: SYSVAR_CLOCK_PUBKEY,
         tokenProgram: TOKEN_PROGRAM_ID,
@@ -758,7 +966,10 @@ describe("serum-gov", () => {
         owner: alice.publicKey,
         authority,
         config,
+        stats,
+        ownerUserAccount: aliceUserAccount,
         redeemTicket: redeemTicket.publicKey,
+        positionHistory: positionHistory(redeemTicket.account.depositAccount),
         srmMint: SRM_MINT,
         srmVault,
         ownerSrmAccount: aliceSRMAccount.publicKey,
@@ -804,7 +1015,10 @@ describe("serum-gov", () => {
         owner: alice.publicKey,
         authority,
         config,
+        stats,
+        ownerUserAccount: aliceUserAccount,
         redeemTicket: redeemTicket.publicKey,
+        positionHistory: positionHistory(redeemTicket.account.depositAccount),
         msrmMint: MSRM_MINT,
         msrmVault,
         ownerMsrmAccount: aliceMSRMAccount.publicKey,
@@ -852,10 +1066,13 @@ describe("serum-gov", () => {
     await program.methods
       .depositVestSrm(new BN(40000 * 1000000))
       .accounts({
+        ownerCheckpoints: aliceCheckpoints,
+        stats,
         payer: alice.publicKey,
         owner: alice.publicKey,
         ownerUserAccount: aliceUserAccount,
         vestAccount: aliceVestAccount,
+        positionHistory: positionHistory(aliceVestAccount),
         claimTicket: claimTicket,
         srmMint: SRM_MINT,
         payerSrmAccount: aliceSRMAccount.publicKey,
@@ -905,10 +1122,13 @@ describe("serum-gov", () => {
     await program.methods
       .depositVestMsrm(new BN(2))
       .accounts({
+        ownerCheckpoints: aliceCheckpoints,
+        stats,
         payer: sbf.publicKey,
         owner: alice.publicKey,
         ownerUserAccount: aliceUserAccount,
         vestAccount: aliceVestAccount,
+        positionHistory: positionHistory(aliceVestAccount),
         claimTicket: claimTicket,
         msrmMint: MSRM_MINT,
         payerMsrmAccount: sbfMsrmAccount,
@@ -939,6 +1159,2540 @@ describe("serum-gov", () => {
     expect(aliceClaimTicket.claimDelay.toNumber()).to.equal(2);
   });
 
+  it("can create voter weight record", async () => {
+    const [voterWeightRecord] = findProgramAddressSync(
+      [
+        Buffer.from("voter_weight_record"),
+        realm.toBuffer(),
+        GSRM_MINT.toBuffer(),
+        alice.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+
+    await program.methods
+      .createVoterWeightRecord(realm, GSRM_MINT)
+      .accounts({
+        payer: alice.publicKey,
+        owner: alice.publicKey,
+        voterWeightRecord,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([alice])
+      .rpc();
+
+    const record = await program.account.voterWeightRecord.fetch(
+      voterWeightRecord
+    );
+    expect(record.realm.toBase58()).to.equal(realm.toBase58());
+    expect(record.governingTokenMint.toBase58()).to.equal(
+      GSRM_MINT.toBase58()
+    );
+    expect(record.governingTokenOwner.toBase58()).to.equal(
+      alice.publicKey.toBase58()
+    );
+    expect(record.voterWeight.toNumber()).to.equal(0);
+  });
+
+  it("can update voter weight record", async () => {
+    const [voterWeightRecord] = findProgramAddressSync(
+      [
+        Buffer.from("voter_weight_record"),
+        realm.toBuffer(),
+        GSRM_MINT.toBuffer(),
+        alice.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+
+    await program.methods
+      .updateVoterWeightRecord()
+      .accounts({
+        config,
+        voterWeightRecord,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      })
+      .remainingAccounts(await aliceVoterAccounts())
+      .rpc();
+
+    // 100 SRM left in LockedAccount, 40000 SRM and 2 MSRM in VestAccounts.
+    const record = await program.account.voterWeightRecord.fetch(
+      voterWeightRecord
+    );
+    expect(record.voterWeight.toString()).to.equal("2040100000000");
+  });
+
+  it("cant update voter weight record with duplicate positions", async () => {
+    const [voterWeightRecord] = findProgramAddressSync(
+      [
+        Buffer.from("voter_weight_record"),
+        realm.toBuffer(),
+        GSRM_MINT.toBuffer(),
+        alice.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+
+    const [aliceVestAccount] = findProgramAddressSync(
+      [
+        Buffer.from("vest_account"),
+        alice.publicKey.toBuffer(),
+        new BN(0).toBuffer("le", 8),
+      ],
+      program.programId
+    );
+
+    try {
+      await program.methods
+        .updateVoterWeightRecord()
+        .accounts({
+          config,
+          voterWeightRecord,
+          clock: SYSVAR_CLOCK_PUBKEY,
+        })
+        .remainingAccounts([
+          { pubkey: aliceUserAccount, isWritable: false, isSigner: false },
+          { pubkey: aliceVestAccount, isWritable: false, isSigner: false },
+          { pubkey: aliceVestAccount, isWritable: false, isSigner: false },
+        ])
+        .rpc();
+      assert(false);
+    } catch (e) {
+      if (e instanceof AnchorError) {
+        assert(true, e.error.errorMessage);
+      } else assert(false);
+    }
+  });
+
+  it("can create and update max voter weight record", async () => {
+    const [maxVoterWeightRecord] = findProgramAddressSync(
+      [
+        Buffer.from("max_voter_weight_record"),
+        realm.toBuffer(),
+        GSRM_MINT.toBuffer(),
+      ],
+      program.programId
+    );
+
+    await program.methods
+      .createMaxVoterWeightRecord(realm, GSRM_MINT)
+      .accounts({
+        payer: alice.publicKey,
+        maxVoterWeightRecord,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([alice])
+      .rpc();
+
+    const updateMaxVoterWeightRecord = () =>
+      program.methods
+        .updateMaxVoterWeightRecord()
+        .accounts({
+          config,
+          maxVoterWeightRecord,
+          stats,
+          clock: SYSVAR_CLOCK_PUBKEY,
+        })
+        .rpc();
+
+    await updateMaxVoterWeightRecord();
+
+    // 40100 SRM and 2 MSRM left in positions.
+    const record = await program.account.maxVoterWeightRecord.fetch(
+      maxVoterWeightRecord
+    );
+    expect(record.realm.toBase58()).to.equal(realm.toBase58());
+    expect(record.maxVoterWeight.toString()).to.equal("2040100000000");
+
+    // Tokens sent straight to the vault don't back any position.
+    await transfer(
+      connection,
+      alice,
+      aliceSRMAccount.publicKey,
+      srmVault,
+      alice,
+      1_000_000
+    );
+    await updateMaxVoterWeightRecord();
+
+    const donatedRecord = await program.account.maxVoterWeightRecord.fetch(
+      maxVoterWeightRecord
+    );
+    expect(donatedRecord.maxVoterWeight.toString()).to.equal("2040100000000");
+  });
+
+  it("can get unburned gsrm at timestamp", async () => {
+    const checkpoints = await program.account.checkpoints.fetch(
+      aliceCheckpoints
+    );
+    expect(checkpoints.owner.toBase58()).to.equal(alice.publicKey.toBase58());
+    expect(checkpoints.unburnedGsrm.toString()).to.equal("2040100000000");
+    expect(checkpoints.untrackedPositions).to.be.false;
+
+    const now = await connection.getBlockTime(await connection.getSlot());
+    const unburnedGsrm = await program.methods
+      .getUnburnedGsrmAt(new BN(now))
+      .accounts({ checkpoints: aliceCheckpoints })
+      .view();
+    expect(unburnedGsrm.toString()).to.equal("2040100000000");
+
+    // Nothing was deposited before the first checkpoint.
+    const initialUnburnedGsrm = await program.methods
+      .getUnburnedGsrmAt(new BN(0))
+      .accounts({ checkpoints: aliceCheckpoints })
+      .view();
+    expect(initialUnburnedGsrm.toNumber()).to.equal(0);
+  });
+
+  it("can delegate voting power", async () => {
+    await program.methods
+      .delegate(sbf.publicKey)
+      .accounts({
+        owner: alice.publicKey,
+        config,
+        ownerUserAccount: aliceUserAccount,
+      })
+      .signers([alice])
+      .rpc();
+
+    const aliceAccount = await program.account.user.fetch(aliceUserAccount);
+    expect(aliceAccount.delegate.toBase58()).to.equal(sbf.publicKey.toBase58());
+  });
+
+  it("can update voter weight record of delegate", async () => {
+    const [voterWeightRecord] = findProgramAddressSync(
+      [
+        Buffer.from("voter_weight_record"),
+        realm.toBuffer(),
+        GSRM_MINT.toBuffer(),
+        sbf.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+
+    await program.methods
+      .createVoterWeightRecord(realm, GSRM_MINT)
+      .accounts({
+        payer: sbf.publicKey,
+        owner: sbf.publicKey,
+        voterWeightRecord,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([sbf])
+      .rpc();
+
+    await program.methods
+      .updateVoterWeightRecord()
+      .accounts({
+        config,
+        voterWeightRecord,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      })
+      .remainingAccounts(await aliceVoterAccounts())
+      .rpc();
+
+    const record = await program.account.voterWeightRecord.fetch(
+      voterWeightRecord
+    );
+    expect(record.voterWeight.toString()).to.equal("2040100000000");
+  });
+
+  it("cant update voter weight record of delegator", async () => {
+    const [voterWeightRecord] = findProgramAddressSync(
+      [
+        Buffer.from("voter_weight_record"),
+        realm.toBuffer(),
+        GSRM_MINT.toBuffer(),
+        alice.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+
+    try {
+      await program.methods
+        .updateVoterWeightRecord()
+        .accounts({
+          config,
+          voterWeightRecord,
+          clock: SYSVAR_CLOCK_PUBKEY,
+        })
+        .remainingAccounts(await aliceVoterAccounts())
+        .rpc();
+      assert(false);
+    } catch (e) {
+      if (e instanceof AnchorError) {
+        expect(e.error.errorCode.code).to.equal("InvalidDelegate");
+      } else assert(false);
+    }
+  });
+
+  it("can undelegate voting power", async () => {
+    await program.methods
+      .undelegate()
+      .accounts({
+        owner: alice.publicKey,
+        config,
+        ownerUserAccount: aliceUserAccount,
+      })
+      .signers([alice])
+      .rpc();
+
+    const aliceAccount = await program.account.user.fetch(aliceUserAccount);
+    expect(aliceAccount.delegate.toBase58()).to.equal(
+      PublicKey.default.toBase58()
+    );
+  });
+
+  it("can update lockup config", async () => {
+    await program.methods
+      .updateLockupConfig(new BN(1000), new BN(20_000))
+      .accounts({
+        config,
+        configAuthority: alice.publicKey,
+        configHistory,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([alice])
+      .rpc();
+
+    const configAccount = await program.account.config.fetch(config);
+    expect(configAccount.maxLockupPeriod.toNumber()).to.equal(1000);
+    expect(configAccount.maxLockupMultiplier.toNumber()).to.equal(20_000);
+  });
+
+  it("cant deposit srm with lockup longer than max lockup period", async () => {
+    const aliceAccount = await program.account.user.fetch(aliceUserAccount);
+    const [aliceLockedAccount] = findProgramAddressSync(
+      [
+        Buffer.from("locked_account"),
+        alice.publicKey.toBuffer(),
+        aliceAccount.lockIndex.toBuffer("le", 8),
+      ],
+      program.programId
+    );
+
+    const [claimTicket] = findProgramAddressSync(
+      [Buffer.from("claim_ticket"), aliceLockedAccount.toBuffer()],
+      program.programId
+    );
+
+    const now = await connection.getBlockTime(await connection.getSlot());
+
+    try {
+      await program.methods
+        .depositLockedSrm(new BN(100_000_000), new BN(now + 2000), 0)
+        .accounts({
+          ownerCheckpoints: aliceCheckpoints,
+          stats,
+          payer: alice.publicKey,
+          owner: alice.publicKey,
+          ownerUserAccount: aliceUserAccount,
+          srmMint: SRM_MINT,
+          payerSrmAccount: aliceSRMAccount.publicKey,
+          authority,
+          config,
+          srmVault,
+          lockedAccount: aliceLockedAccount,
+          positionHistory: positionHistory(aliceLockedAccount),
+          claimTicket: claimTicket,
+          clock: SYSVAR_CLOCK_PUBKEY,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([alice])
+        .rpc();
+      assert(false);
+    } catch (e) {
+      if (e instanceof AnchorError) {
+        assert(true, e.error.errorMessage);
+      } else assert(false);
+    }
+  });
+
+  it("can deposit srm with lockup", async () => {
+    const aliceAccount = await program.account.user.fetch(aliceUserAccount);
+    const [aliceLockedAccount] = findProgramAddressSync(
+      [
+        Buffer.from("locked_account"),
+        alice.publicKey.toBuffer(),
+        aliceAccount.lockIndex.toBuffer("le", 8),
+      ],
+      program.programId
+    );
+
+    const [claimTicket] = findProgramAddressSync(
+      [Buffer.from("claim_ticket"), aliceLockedAccount.toBuffer()],
+      program.programId
+    );
+
+    const now = await connection.getBlockTime(await connection.getSlot());
+
+    await program.methods
+      .depositLockedSrm(new BN(100_000_000), new BN(now + 500), 0)
+      .accounts({
+        ownerCheckpoints: aliceCheckpoints,
+        stats,
+        payer: alice.publicKey,
+        owner: alice.publicKey,
+        ownerUserAccount: aliceUserAccount,
+        srmMint: SRM_MINT,
+        payerSrmAccount: aliceSRMAccount.publicKey,
+        authority,
+        config,
+        srmVault,
+        lockedAccount: aliceLockedAccount,
+        positionHistory: positionHistory(aliceLockedAccount),
+        claimTicket: claimTicket,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([alice])
+      .rpc();
+
+    const lockedAccount = await program.account.lockedAccount.fetch(
+      aliceLockedAccount
+    );
+    expect(lockedAccount.lockupEnd.toNumber()).to.equal(now + 500);
+    expect(lockedAccount.totalGsrmAmount.toNumber()).to.equal(100_000_000);
+
+    // Half of the max lockup period at a 2x max multiplier is about 1.5x,
+    // with the bonus decaying by 0.1 SRM a second.
+    const lockupWeight = async () => {
+      const before = await connection.getBlockTime(await connection.getSlot());
+      const weight = await aliceWeightOf([aliceLockedAccount]);
+      const after = await connection.getBlockTime(await connection.getSlot());
+
+      expect(weight.toNumber()).to.be.within(
+        100_000_000 + 100_000 * (now + 500 - after - 1),
+        100_000_000 + 100_000 * (now + 500 - before + 1)
+      );
+      return weight;
+    };
+
+    const weight = await lockupWeight();
+    expect(weight.toNumber()).to.be.greaterThan(140_000_000);
+
+    await sleep(2);
+    const decayedWeight = await lockupWeight();
+    expect(decayedWeight.lt(weight)).to.be.true;
+  });
+
+  it("cant burn gsrm before lockup ends", async () => {
+    const [aliceLockedAccount] = findProgramAddressSync(
+      [
+        Buffer.from("locked_account"),
+        alice.publicKey.toBuffer(),
+        new BN(2).toBuffer("le", 8),
+      ],
+      program.programId
+    );
+
+    const [redeemTicket] = findProgramAddressSync(
+      [
+        Buffer.from("redeem_ticket"),
+        aliceLockedAccount.toBuffer(),
+        new BN(0).toBuffer("le", 8),
+      ],
+      program.programId
+    );
+
+    try {
+      await program.methods
+        .burnLockedGsrm(new BN(100_000_000))
+        .accounts({
+          ownerCheckpoints: aliceCheckpoints,
+          stats,
+          ownerUserAccount: aliceUserAccount,
+          owner: alice.publicKey,
+          authority,
+          config,
+          gsrmMint: GSRM_MINT,
+          ownerGsrmAccount: aliceGSRMAccount.publicKey,
+          lockedAccount: aliceLockedAccount,
+          positionHistory: positionHistory(aliceLockedAccount),
+          redeemTicket: redeemTicket,
+          clock: SYSVAR_CLOCK_PUBKEY,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([alice])
+        .rpc();
+      assert(false);
+    } catch (e) {
+      if (e instanceof AnchorError) {
+        expect(e.error.errorCode.code).to.equal("LockupNotEnded");
+      } else assert(false);
+    }
+  });
+
+  it("cant update vest weight config with weight over 100%", async () => {
+    try {
+      await program.methods
+        .updateVestWeightConfig({ discounted: {} }, new BN(20_000))
+        .accounts({
+          config,
+          configAuthority: alice.publicKey,
+          configHistory,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([alice])
+        .rpc();
+      assert(false);
+    } catch (e) {
+      if (e instanceof AnchorError) {
+        assert(true, e.error.errorMessage);
+      } else assert(false);
+    }
+  });
+
+  it("can update vest weight config", async () => {
+    await program.methods
+      .updateVestWeightConfig({ discounted: {} }, new BN(5_000))
+      .accounts({
+        config,
+        configAuthority: alice.publicKey,
+        configHistory,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([alice])
+      .rpc();
+
+    const configAccount = await program.account.config.fetch(config);
+    expect(configAccount.vestWeightMode).to.deep.equal({ discounted: {} });
+    expect(configAccount.unvestedWeight.toNumber()).to.equal(5_000);
+  });
+
+  it("can update governance config", async () => {
+    await program.methods
+      .updateGovernanceConfig(governanceProgram, realm, GSRM_MINT)
+      .accounts({
+        config,
+        configAuthority: alice.publicKey,
+        configHistory,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([alice])
+      .rpc();
+
+    const configAccount = await program.account.config.fetch(config);
+    expect(configAccount.governanceProgram.toBase58()).to.equal(
+      governanceProgram.toBase58()
+    );
+    expect(configAccount.realm.toBase58()).to.equal(realm.toBase58());
+    expect(configAccount.governingTokenMint.toBase58()).to.equal(
+      GSRM_MINT.toBase58()
+    );
+  });
+
+  it("cant burn gsrm without token owner record", async () => {
+    const [aliceLockedAccount] = findProgramAddressSync(
+      [
+        Buffer.from("locked_account"),
+        alice.publicKey.toBuffer(),
+        new BN(0).toBuffer("le", 8),
+      ],
+      program.programId
+    );
+
+    const [redeemTicket] = findProgramAddressSync(
+      [
+        Buffer.from("redeem_ticket"),
+        aliceLockedAccount.toBuffer(),
+        new BN(1).toBuffer("le", 8),
+      ],
+      program.programId
+    );
+
+    try {
+      await program.methods
+        .burnLockedGsrm(new BN(50_000_000))
+        .accounts({
+          owner: alice.publicKey,
+          authority,
+          config,
+          gsrmMint: GSRM_MINT,
+          ownerGsrmAccount: aliceGSRMAccount.publicKey,
+          lockedAccount: aliceLockedAccount,
+          positionHistory: positionHistory(aliceLockedAccount),
+          ownerCheckpoints: aliceCheckpoints,
+          stats,
+          ownerUserAccount: aliceUserAccount,
+          redeemTicket: redeemTicket,
+          clock: SYSVAR_CLOCK_PUBKEY,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([alice])
+        .rpc();
+      assert(false);
+    } catch (e) {
+      if (e instanceof AnchorError) {
+        expect(e.error.errorCode.code).to.equal("InvalidTokenOwnerRecord");
+      } else assert(false);
+    }
+  });
+
+  it("can delegate while owner has no live votes", async () => {
+    await program.methods
+      .delegate(carol.publicKey)
+      .accounts({
+        owner: alice.publicKey,
+        config,
+        ownerUserAccount: aliceUserAccount,
+      })
+      .remainingAccounts([
+        {
+          pubkey: tokenOwnerRecord(alice.publicKey),
+          isWritable: false,
+          isSigner: false,
+        },
+      ])
+      .signers([alice])
+      .rpc();
+
+    const aliceAccount = await program.account.user.fetch(aliceUserAccount);
+    expect(aliceAccount.delegate.toBase58()).to.equal(
+      carol.publicKey.toBase58()
+    );
+  });
+
+  it("cant re-delegate during a live vote of the delegate", async () => {
+    const tokenOwnerRecords = [alice.publicKey, carol.publicKey].map(
+      (owner) => ({
+        pubkey: tokenOwnerRecord(owner),
+        isWritable: false,
+        isSigner: false,
+      })
+    );
+
+    try {
+      await program.methods
+        .delegate(sbf.publicKey)
+        .accounts({
+          owner: alice.publicKey,
+          config,
+          ownerUserAccount: aliceUserAccount,
+        })
+        .remainingAccounts(tokenOwnerRecords)
+        .signers([alice])
+        .rpc();
+      assert(false);
+    } catch (e) {
+      if (e instanceof AnchorError) {
+        expect(e.error.errorCode.code).to.equal("ActiveVotes");
+      } else assert(false);
+    }
+
+    try {
+      await program.methods
+        .undelegate()
+        .accounts({
+          owner: alice.publicKey,
+          config,
+          ownerUserAccount: aliceUserAccount,
+        })
+        .remainingAccounts(tokenOwnerRecords)
+        .signers([alice])
+        .rpc();
+      assert(false);
+    } catch (e) {
+      if (e instanceof AnchorError) {
+        expect(e.error.errorCode.code).to.equal("ActiveVotes");
+      } else assert(false);
+    }
+  });
+
+  it("cant burn gsrm while delegate has unrelinquished votes", async () => {
+    const [aliceLockedAccount] = findProgramAddressSync(
+      [
+        Buffer.from("locked_account"),
+        alice.publicKey.toBuffer(),
+        new BN(0).toBuffer("le", 8),
+      ],
+      program.programId
+    );
+
+    const [redeemTicket] = findProgramAddressSync(
+      [
+        Buffer.from("redeem_ticket"),
+        aliceLockedAccount.toBuffer(),
+        new BN(1).toBuffer("le", 8),
+      ],
+      program.programId
+    );
+
+    try {
+      await program.methods
+        .burnLockedGsrm(new BN(50_000_000))
+        .accounts({
+          owner: alice.publicKey,
+          authority,
+          config,
+          gsrmMint: GSRM_MINT,
+          ownerGsrmAccount: aliceGSRMAccount.publicKey,
+          lockedAccount: aliceLockedAccount,
+          positionHistory: positionHistory(aliceLockedAccount),
+          ownerCheckpoints: aliceCheckpoints,
+          stats,
+          ownerUserAccount: aliceUserAccount,
+          redeemTicket: redeemTicket,
+          clock: SYSVAR_CLOCK_PUBKEY,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(
+          [alice.publicKey, carol.publicKey].map((owner) => ({
+            pubkey: tokenOwnerRecord(owner),
+            isWritable: false,
+            isSigner: false,
+          }))
+        )
+        .signers([alice])
+        .rpc();
+      assert(false);
+    } catch (e) {
+      if (e instanceof AnchorError) {
+        expect(e.error.errorCode.code).to.equal("ActiveVotes");
+      } else assert(false);
+    }
+  });
+
+  it("can disable governance config", async () => {
+    await program.methods
+      .updateGovernanceConfig(
+        PublicKey.default,
+        PublicKey.default,
+        PublicKey.default
+      )
+      .accounts({
+        config,
+        configAuthority: alice.publicKey,
+        configHistory,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([alice])
+      .rpc();
+
+    const configAccount = await program.account.config.fetch(config);
+    expect(configAccount.governanceProgram.toBase58()).to.equal(
+      PublicKey.default.toBase58()
+    );
+  });
+
+  it("can undelegate once governance config is disabled", async () => {
+    await program.methods
+      .undelegate()
+      .accounts({
+        owner: alice.publicKey,
+        config,
+        ownerUserAccount: aliceUserAccount,
+      })
+      .signers([alice])
+      .rpc();
+
+    const aliceAccount = await program.account.user.fetch(aliceUserAccount);
+    expect(aliceAccount.delegate.toBase58()).to.equal(
+      PublicKey.default.toBase58()
+    );
+  });
+
+  it("positions younger than min position age have no voting weight", async () => {
+    await program.methods
+      .updateMinPositionAge(new BN(1_000_000))
+      .accounts({
+        config,
+        configAuthority: alice.publicKey,
+        configHistory,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([alice])
+      .rpc();
+
+    const configAccount = await program.account.config.fetch(config);
+    expect(configAccount.minPositionAge.toNumber()).to.equal(1_000_000);
+
+    const [voterWeightRecord] = findProgramAddressSync(
+      [
+        Buffer.from("voter_weight_record"),
+        realm.toBuffer(),
+        GSRM_MINT.toBuffer(),
+        alice.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+
+    await program.methods
+      .updateVoterWeightRecord()
+      .accounts({
+        config,
+        voterWeightRecord,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      })
+      .remainingAccounts(await aliceVoterAccounts())
+      .rpc();
+
+    const record = await program.account.voterWeightRecord.fetch(
+      voterWeightRecord
+    );
+    expect(record.voterWeight.toNumber()).to.equal(0);
+
+    await program.methods
+      .updateMinPositionAge(new BN(0))
+      .accounts({
+        config,
+        configAuthority: alice.publicKey,
+        configHistory,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([alice])
+      .rpc();
+  });
+
+  it("cant cap owner weight at 0", async () => {
+    try {
+      await program.methods
+        .updateWeightCurve({ capped: {} }, new BN(0))
+        .accounts({
+          config,
+          configAuthority: alice.publicKey,
+          configHistory,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([alice])
+        .rpc();
+      assert(false);
+    } catch (e) {
+      if (e instanceof AnchorError) {
+        expect(e.error.errorCode.code).to.equal("InvalidWeightCurveConfig");
+      } else assert(false);
+    }
+  });
+
+  it("can apply weight curves to voter weight", async () => {
+    const [voterWeightRecord] = findProgramAddressSync(
+      [
+        Buffer.from("voter_weight_record"),
+        realm.toBuffer(),
+        GSRM_MINT.toBuffer(),
+        alice.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+
+    const updateVoterWeight = async (weightCurve: any, maxOwnerWeight: BN) => {
+      await program.methods
+        .updateWeightCurve(weightCurve, maxOwnerWeight)
+        .accounts({
+          config,
+          configAuthority: alice.publicKey,
+          configHistory,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([alice])
+        .rpc();
+
+      await program.methods
+        .updateVoterWeightRecord()
+        .accounts({
+          config,
+          voterWeightRecord,
+          clock: SYSVAR_CLOCK_PUBKEY,
+        })
+        .remainingAccounts(await aliceVoterAccounts())
+        .rpc();
+
+      const record = await program.account.voterWeightRecord.fetch(
+        voterWeightRecord
+      );
+      return record.voterWeight;
+    };
+
+    const linearWeight = await updateVoterWeight({ linear: {} }, new BN(0));
+
+    const cappedWeight = await updateVoterWeight(
+      { capped: {} },
+      new BN(1_000_000)
+    );
+    expect(cappedWeight.toString()).to.equal("1000000");
+
+    const squareRootWeight = await updateVoterWeight(
+      { squareRoot: {} },
+      new BN(0)
+    );
+    expect(squareRootWeight.gtn(0)).to.equal(true);
+    expect(squareRootWeight.lt(linearWeight)).to.equal(true);
+
+    const configAccount = await program.account.config.fetch(config);
+    expect(configAccount.weightCurve).to.deep.equal({ squareRoot: {} });
+
+    await updateVoterWeight({ linear: {} }, new BN(0));
+  });
+
+  it("cant update max voter weight record under a non linear weight curve", async () => {
+    const [maxVoterWeightRecord] = findProgramAddressSync(
+      [
+        Buffer.from("max_voter_weight_record"),
+        realm.toBuffer(),
+        GSRM_MINT.toBuffer(),
+      ],
+      program.programId
+    );
+
+    const updateWeightCurve = (weightCurve: any, maxOwnerWeight: BN) =>
+      program.methods
+        .updateWeightCurve(weightCurve, maxOwnerWeight)
+        .accounts({
+          config,
+          configAuthority: alice.publicKey,
+          configHistory,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([alice])
+        .rpc();
+    const updateMaxVoterWeightRecord = () =>
+      program.methods
+        .updateMaxVoterWeightRecord()
+        .accounts({
+          config,
+          maxVoterWeightRecord,
+          stats,
+          clock: SYSVAR_CLOCK_PUBKEY,
+        })
+        .rpc();
+
+    for (const [weightCurve, maxOwnerWeight] of [
+      [{ squareRoot: {} }, new BN(0)],
+      [{ capped: {} }, new BN(1_000_000)],
+    ] as [any, BN][]) {
+      await updateWeightCurve(weightCurve, maxOwnerWeight);
+      try {
+        await updateMaxVoterWeightRecord();
+        assert(false);
+      } catch (e) {
+        if (e instanceof AnchorError) {
+          expect(e.error.errorCode.code).to.equal("NonLinearWeightCurve");
+        } else assert(false);
+      }
+    }
+
+    await updateWeightCurve({ linear: {} }, new BN(0));
+    await updateMaxVoterWeightRecord();
+  });
+
+  it("emits events for config updates", async () => {
+    let event: any;
+    const listener = program.addEventListener(
+      "UpdateMinPositionAgeEvent",
+      (e) => {
+        event = e;
+      }
+    );
+
+    await program.methods
+      .updateMinPositionAge(new BN(0))
+      .accounts({
+        config,
+        configAuthority: alice.publicKey,
+        configHistory,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([alice])
+      .rpc();
+
+    await sleep(2);
+    await program.removeEventListener(listener);
+
+    expect(event.minPositionAge.toNumber()).to.equal(0);
+  });
+
+  it("keeps stats in sync with positions and tickets", async () => {
+    const sum = (amounts: BN[]) =>
+      amounts.reduce((total, amount) => total.add(amount), new BN(0));
+    const unburned = (account: any) =>
+      account.isMsrm
+        ? account.totalGsrmAmount
+            .sub(account.gsrmBurned)
+            .div(new BN(MSRM_MULTIPLIER))
+        : account.totalGsrmAmount.sub(account.gsrmBurned);
+
+    const lockedAccounts = await program.account.lockedAccount.all();
+    const vestAccounts = await program.account.vestAccount.all();
+    const claimTickets = await program.account.claimTicket.all();
+    const redeemTickets = await program.account.redeemTicket.all();
+
+    const statsAccount = await program.account.stats.fetch(stats);
+
+    expect(statsAccount.srmLocked.toString()).to.equal(
+      sum(
+        lockedAccounts
+          .filter((a) => !a.account.isMsrm)
+          .map((a) => unburned(a.account))
+      ).toString()
+    );
+    expect(statsAccount.msrmLocked.toString()).to.equal(
+      sum(
+        lockedAccounts
+          .filter((a) => a.account.isMsrm)
+          .map((a) => unburned(a.account))
+      ).toString()
+    );
+    expect(statsAccount.srmVesting.toString()).to.equal(
+      sum(
+        vestAccounts
+          .filter((a) => !a.account.isMsrm)
+          .map((a) => unburned(a.account))
+      ).toString()
+    );
+    expect(statsAccount.msrmVesting.toString()).to.equal(
+      sum(
+        vestAccounts
+          .filter((a) => a.account.isMsrm)
+          .map((a) => unburned(a.account))
+      ).toString()
+    );
+    expect(statsAccount.gsrmUnclaimed.toString()).to.equal(
+      sum(claimTickets.map((t) => t.account.gsrmAmount)).toString()
+    );
+    expect(statsAccount.srmRedeemable.toString()).to.equal(
+      sum(
+        redeemTickets
+          .filter((t) => !t.account.isMsrm)
+          .map((t) => t.account.amount)
+      ).toString()
+    );
+    expect(statsAccount.msrmRedeemable.toString()).to.equal(
+      sum(
+        redeemTickets
+          .filter((t) => t.account.isMsrm)
+          .map((t) => t.account.amount)
+      ).toString()
+    );
+
+    // Plus the SRM sent straight to the vault when updating the max voter weight record.
+    const vaultSrm = await connection.getTokenAccountBalance(srmVault);
+    expect(vaultSrm.value.amount).to.equal(
+      statsAccount.srmLocked
+        .add(statsAccount.srmVesting)
+        .add(statsAccount.srmRedeemable)
+        .add(new BN(1_000_000))
+        .toString()
+    );
+  });
+
+  it("keeps user totals in sync with positions and tickets", async () => {
+    const ownedBy = (owner: PublicKey) => [
+      {
+        memcmp: {
+          offset: 8,
+          bytes: owner.toBase58(),
+        },
+      },
+    ];
+    const sum = (amounts: BN[]) =>
+      amounts.reduce((total, amount) => total.add(amount), new BN(0));
+
+    const lockedAccounts = await program.account.lockedAccount.all(
+      ownedBy(alice.publicKey)
+    );
+    const vestAccounts = await program.account.vestAccount.all(
+      ownedBy(alice.publicKey)
+    );
+    const claimTickets = await program.account.claimTicket.all(
+      ownedBy(alice.publicKey)
+    );
+    const redeemTickets = await program.account.redeemTicket.all(
+      ownedBy(alice.publicKey)
+    );
+
+    const userAccount = await program.account.user.fetch(aliceUserAccount);
+
+    expect(userAccount.srmLocked.toString()).to.equal(
+      sum(
+        lockedAccounts
+          .filter((a) => !a.account.isMsrm)
+          .map((a) => a.account.totalGsrmAmount.sub(a.account.gsrmBurned))
+      ).toString()
+    );
+    expect(userAccount.srmVesting.toString()).to.equal(
+      sum(
+        vestAccounts
+          .filter((a) => !a.account.isMsrm)
+          .map((a) => a.account.totalGsrmAmount.sub(a.account.gsrmBurned))
+      ).toString()
+    );
+    expect(userAccount.gsrmUnclaimed.toString()).to.equal(
+      sum(claimTickets.map((t) => t.account.gsrmAmount)).toString()
+    );
+    expect(userAccount.openClaimTickets.toNumber()).to.equal(
+      claimTickets.length
+    );
+    expect(userAccount.openRedeemTickets.toNumber()).to.equal(
+      redeemTickets.length
+    );
+  });
+
+  it("records position history", async () => {
+    const [aliceLockedAccount] = findProgramAddressSync(
+      [
+        Buffer.from("locked_account"),
+        alice.publicKey.toBuffer(),
+        new BN(0).toBuffer("le", 8),
+      ],
+      program.programId
+    );
+
+    const history = await program.account.positionHistory.fetch(
+      positionHistory(aliceLockedAccount)
+    );
+    expect(history.position.toBase58()).to.equal(
+      aliceLockedAccount.toBase58()
+    );
+
+    const kinds = history.actions.map((a) => Object.keys(a.kind)[0]);
+    expect(kinds.slice(0, 3)).to.deep.equal(["deposit", "claim", "burn"]);
+    expect(history.actions[0].amount.toString()).to.equal("200000000");
+    expect(history.actions[2].amount.toString()).to.equal("100000000");
+  });
+
+  it("can close fully burned position", async () => {
+    const lockedAccounts = await program.account.lockedAccount.all([
+      {
+        memcmp: {
+          offset: 8,
+          bytes: alice.publicKey.toBase58(),
+        },
+      },
+    ]);
+    const position = lockedAccounts.find(
+      (a) => a.account.status.fullyBurned !== undefined
+    );
+    expect(position).to.not.be.undefined;
+
+    const [claimTicket] = findProgramAddressSync(
+      [Buffer.from("claim_ticket"), position.publicKey.toBuffer()],
+      program.programId
+    );
+    const redeemTickets = [
+      ...Array(position.account.redeemIndex.toNumber()).keys(),
+    ].map(
+      (i) =>
+        findProgramAddressSync(
+          [
+            Buffer.from("redeem_ticket"),
+            position.publicKey.toBuffer(),
+            new BN(i).toBuffer("le", 8),
+          ],
+          program.programId
+        )[0]
+    );
+
+    await program.methods
+      .closePosition()
+      .accounts({
+        position: position.publicKey,
+        rentPayer: position.account.rentPayer,
+        positionHistory: positionHistory(position.publicKey),
+        ownerUserAccount: aliceUserAccount,
+      })
+      .remainingAccounts(
+        [claimTicket, ...redeemTickets].map((pubkey) => ({
+          pubkey,
+          isWritable: false,
+          isSigner: false,
+        }))
+      )
+      .rpc();
+
+    const closed = await program.account.lockedAccount.fetchNullable(
+      position.publicKey
+    );
+    expect(closed).to.be.null;
+    expect(
+      await connection.getAccountInfo(positionHistory(position.publicKey))
+    ).to.be.null;
+  });
+
+  it("cant close active position", async () => {
+    const lockedAccounts = await program.account.lockedAccount.all([
+      {
+        memcmp: {
+          offset: 8,
+          bytes: alice.publicKey.toBase58(),
+        },
+      },
+    ]);
+    const position = lockedAccounts.find(
+      (a) => a.account.status.active !== undefined
+    );
+
+    try {
+      await program.methods
+        .closePosition()
+        .accounts({
+          position: position.publicKey,
+          rentPayer: position.account.rentPayer,
+          positionHistory: positionHistory(position.publicKey),
+          ownerUserAccount: aliceUserAccount,
+        })
+        .rpc();
+      assert(false);
+    } catch (e) {
+      if (e instanceof AnchorError) {
+        expect(e.error.errorCode.code).to.equal("PositionNotFullyBurned");
+      } else assert(false);
+    }
+  });
+
+  it("cant close user with open positions", async () => {
+    try {
+      await program.methods
+        .closeUser()
+        .accounts({
+          owner: alice.publicKey,
+          ownerUserAccount: aliceUserAccount,
+          ownerTombstone: userTombstone(alice.publicKey),
+          ownerCheckpoints: checkpointsOf(alice.publicKey),
+          checkpointsRentPayer: alice.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([alice])
+        .rpc();
+      assert(false);
+    } catch (e) {
+      if (e instanceof AnchorError) {
+        expect(e.error.errorCode.code).to.equal("OpenPositions");
+      } else assert(false);
+    }
+  });
+
+  it("can close and re-create user", async () => {
+    const [sbfUserAccount] = findProgramAddressSync(
+      [Buffer.from("user"), sbf.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initUser(sbf.publicKey)
+      .accounts({
+        payer: sbf.publicKey,
+        userAccount: sbfUserAccount,
+        userTombstone: userTombstone(sbf.publicKey),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([sbf])
+      .rpc();
+
+    await program.methods
+      .closeUser()
+      .accounts({
+        owner: sbf.publicKey,
+        ownerUserAccount: sbfUserAccount,
+        ownerTombstone: userTombstone(sbf.publicKey),
+        ownerCheckpoints: checkpointsOf(sbf.publicKey),
+        checkpointsRentPayer: sbf.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([sbf])
+      .rpc();
+
+    expect(await program.account.user.fetchNullable(sbfUserAccount)).to.be
+      .null;
+    const tombstone = await program.account.userTombstone.fetch(
+      userTombstone(sbf.publicKey)
+    );
+    expect(tombstone.lockIndex.toNumber()).to.equal(0);
+
+    await program.methods
+      .initUser(sbf.publicKey)
+      .accounts({
+        payer: sbf.publicKey,
+        userAccount: sbfUserAccount,
+        userTombstone: userTombstone(sbf.publicKey),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([sbf])
+      .rpc();
+
+    const sbfAccount = await program.account.user.fetch(sbfUserAccount);
+    expect(sbfAccount.lockIndex.toNumber()).to.equal(
+      tombstone.lockIndex.toNumber()
+    );
+    expect(sbfAccount.vestIndex.toNumber()).to.equal(
+      tombstone.vestIndex.toNumber()
+    );
+  });
+
+  it("refunds checkpoints to whoever paid for them when closing user", async () => {
+    const [sbfUserAccount] = findProgramAddressSync(
+      [Buffer.from("user"), sbf.publicKey.toBuffer()],
+      program.programId
+    );
+    const sbfAccount = await program.account.user.fetch(sbfUserAccount);
+    const [sbfLockedAccount] = findProgramAddressSync(
+      [
+        Buffer.from("locked_account"),
+        sbf.publicKey.toBuffer(),
+        sbfAccount.lockIndex.toBuffer("le", 8),
+      ],
+      program.programId
+    );
+    const [redeemTicket] = findProgramAddressSync(
+      [
+        Buffer.from("redeem_ticket"),
+        sbfLockedAccount.toBuffer(),
+        new BN(0).toBuffer("le", 8),
+      ],
+      program.programId
+    );
+    const sbfGsrmAccount = await createAccount(
+      connection,
+      sbf,
+      GSRM_MINT,
+      sbf.publicKey
+    );
+
+    // Alice sponsors a deposit of sbf, which creates the Checkpoints of sbf.
+    await program.methods
+      .depositLockedSrm(new BN(100_000_000), new BN(0), 0)
+      .accounts({
+        ownerCheckpoints: checkpointsOf(sbf.publicKey),
+        stats,
+        payer: alice.publicKey,
+        owner: sbf.publicKey,
+        ownerUserAccount: sbfUserAccount,
+        srmMint: SRM_MINT,
+        payerSrmAccount: aliceSRMAccount.publicKey,
+        authority,
+        config,
+        srmVault,
+        lockedAccount: sbfLockedAccount,
+        positionHistory: positionHistory(sbfLockedAccount),
+        claimTicket: claimTicketOf(sbfLockedAccount),
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([alice])
+      .rpc();
+
+    const checkpoints = await program.account.checkpoints.fetch(
+      checkpointsOf(sbf.publicKey)
+    );
+    expect(checkpoints.rentPayer.toBase58()).to.equal(
+      alice.publicKey.toBase58()
+    );
+
+    await sleep(3);
+    await program.methods
+      .claim()
+      .accounts({
+        ownerCheckpoints: checkpointsOf(sbf.publicKey),
+        stats,
+        ownerUserAccount: sbfUserAccount,
+        owner: sbf.publicKey,
+        claimTicket: claimTicketOf(sbfLockedAccount),
+        rentPayer: alice.publicKey,
+        positionHistory: positionHistory(sbfLockedAccount),
+        authority,
+        gsrmMint: GSRM_MINT,
+        ownerGsrmAccount: sbfGsrmAccount,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([sbf])
+      .rpc();
+
+    await program.methods
+      .burnLockedGsrm(new BN(100_000_000))
+      .accounts({
+        ownerCheckpoints: checkpointsOf(sbf.publicKey),
+        stats,
+        ownerUserAccount: sbfUserAccount,
+        owner: sbf.publicKey,
+        authority,
+        config,
+        gsrmMint: GSRM_MINT,
+        ownerGsrmAccount: sbfGsrmAccount,
+        lockedAccount: sbfLockedAccount,
+        positionHistory: positionHistory(sbfLockedAccount),
+        redeemTicket,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([sbf])
+      .rpc();
+
+    await sleep(3);
+    await program.methods
+      .redeemSrm()
+      .accounts({
+        owner: sbf.publicKey,
+        authority,
+        config,
+        stats,
+        ownerUserAccount: sbfUserAccount,
+        redeemTicket,
+        positionHistory: positionHistory(sbfLockedAccount),
+        srmMint: SRM_MINT,
+        srmVault,
+        ownerSrmAccount: sbfSrmAccount,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([sbf])
+      .rpc();
+
+    await program.methods
+      .closePosition()
+      .accounts({
+        position: sbfLockedAccount,
+        rentPayer: alice.publicKey,
+        positionHistory: positionHistory(sbfLockedAccount),
+        ownerUserAccount: sbfUserAccount,
+      })
+      .remainingAccounts(
+        [claimTicketOf(sbfLockedAccount), redeemTicket].map((pubkey) => ({
+          pubkey,
+          isWritable: false,
+          isSigner: false,
+        }))
+      )
+      .rpc();
+
+    const closeUser = (checkpointsRentPayer: PublicKey) =>
+      program.methods
+        .closeUser()
+        .accounts({
+          owner: sbf.publicKey,
+          ownerUserAccount: sbfUserAccount,
+          ownerTombstone: userTombstone(sbf.publicKey),
+          ownerCheckpoints: checkpointsOf(sbf.publicKey),
+          checkpointsRentPayer,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([sbf])
+        .rpc();
+
+    try {
+      await closeUser(sbf.publicKey);
+      assert(false);
+    } catch (e) {
+      if (e instanceof AnchorError) {
+        expect(e.error.errorCode.code).to.equal("InvalidRentPayer");
+      } else assert(false);
+    }
+
+    const aliceLamports = await connection.getBalance(alice.publicKey);
+    await closeUser(alice.publicKey);
+
+    expect(await connection.getAccountInfo(checkpointsOf(sbf.publicKey))).to
+      .be.null;
+    expect(await connection.getBalance(alice.publicKey)).to.be.greaterThan(
+      aliceLamports
+    );
+  });
+
+  it("can close migrated user with positions closed before tracking", async () => {
+    const [daveUserAccount] = findProgramAddressSync(
+      [Buffer.from("user"), dave.publicKey.toBuffer()],
+      program.programId
+    );
+    const [daveLockedAccount] = findProgramAddressSync(
+      [
+        Buffer.from("locked_account"),
+        dave.publicKey.toBuffer(),
+        new BN(0).toBuffer("le", 8),
+      ],
+      program.programId
+    );
+
+    await connection.confirmTransaction(
+      await connection.requestAirdrop(dave.publicKey, LAMPORTS_PER_SOL)
+    );
+
+    await program.methods
+      .migrateAccount()
+      .accounts({
+        payer: dave.publicKey,
+        account: daveUserAccount,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([dave])
+      .rpc();
+
+    const daveAccount = await program.account.user.fetch(daveUserAccount);
+    expect(daveAccount.lockIndex.toNumber()).to.equal(1);
+    expect(daveAccount.closedPositions.toNumber()).to.equal(0);
+
+    const closeUser = (positions: PublicKey[]) =>
+      program.methods
+        .closeUser()
+        .accounts({
+          owner: dave.publicKey,
+          ownerUserAccount: daveUserAccount,
+          ownerTombstone: userTombstone(dave.publicKey),
+          ownerCheckpoints: checkpointsOf(dave.publicKey),
+          checkpointsRentPayer: dave.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(
+          positions.map((pubkey) => ({
+            pubkey,
+            isWritable: false,
+            isSigner: false,
+          }))
+        )
+        .signers([dave])
+        .rpc();
+
+    try {
+      await closeUser([]);
+      assert(false);
+    } catch (e) {
+      if (e instanceof AnchorError) {
+        expect(e.error.errorCode.code).to.equal("OpenPositions");
+      } else assert(false);
+    }
+
+    await closeUser([daveLockedAccount]);
+
+    expect(await program.account.user.fetchNullable(daveUserAccount)).to.be
+      .null;
+  });
+
+  it("cant get unburned gsrm from before checkpoints of older positions", async () => {
+    const [daveUserAccount] = findProgramAddressSync(
+      [Buffer.from("user"), dave.publicKey.toBuffer()],
+      program.programId
+    );
+    const [daveCheckpoints] = findProgramAddressSync(
+      [Buffer.from("checkpoints"), dave.publicKey.toBuffer()],
+      program.programId
+    );
+    const [daveLockedAccount] = findProgramAddressSync(
+      [
+        Buffer.from("locked_account"),
+        dave.publicKey.toBuffer(),
+        new BN(1).toBuffer("le", 8),
+      ],
+      program.programId
+    );
+
+    await program.methods
+      .initUser(dave.publicKey)
+      .accounts({
+        payer: dave.publicKey,
+        userAccount: daveUserAccount,
+        userTombstone: userTombstone(dave.publicKey),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([dave])
+      .rpc();
+
+    // Dave's LockedAccount at index 0 was opened before checkpoints were tracked.
+    await program.methods
+      .depositLockedSrm(new BN(100_000_000), new BN(0), 0)
+      .accounts({
+        ownerCheckpoints: daveCheckpoints,
+        stats,
+        payer: alice.publicKey,
+        owner: dave.publicKey,
+        ownerUserAccount: daveUserAccount,
+        srmMint: SRM_MINT,
+        payerSrmAccount: aliceSRMAccount.publicKey,
+        authority,
+        config,
+        srmVault,
+        lockedAccount: daveLockedAccount,
+        positionHistory: positionHistory(daveLockedAccount),
+        claimTicket: claimTicketOf(daveLockedAccount),
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([alice])
+      .rpc();
+
+    const checkpoints = await program.account.checkpoints.fetch(
+      daveCheckpoints
+    );
+    expect(checkpoints.untrackedPositions).to.be.true;
+    expect(checkpoints.unburnedGsrm.toNumber()).to.equal(100_000_000);
+
+    try {
+      await program.methods
+        .getUnburnedGsrmAt(new BN(0))
+        .accounts({ checkpoints: daveCheckpoints })
+        .view();
+      assert(false);
+    } catch (e) {
+      expect(e.toString()).to.contain("CheckpointNotFound");
+    }
+  });
+
+  it("can audit vaults", async () => {
+    await program.methods
+      .auditVaults(true)
+      .accounts({
+        config,
+        stats,
+        srmVault,
+        msrmVault,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      })
+      .rpc();
+  });
+
+  it("undercounted stats dont block exits", async () => {
+    const seedStats = (totals: BN[]) =>
+      program.methods
+        .initStats(...(totals as [BN, BN, BN, BN, BN, BN, BN]))
+        .accounts({
+          configAuthority: alice.publicKey,
+          config,
+          stats,
+          configHistory,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([alice])
+        .rpc();
+    const auditVaults = () =>
+      program.methods
+        .auditVaults(true)
+        .accounts({
+          config,
+          stats,
+          srmVault,
+          msrmVault,
+          clock: SYSVAR_CLOCK_PUBKEY,
+        })
+        .rpc();
+
+    const position = await depositLockedSrmForTier(0);
+    const before = await program.account.stats.fetch(stats);
+
+    // Seeded too low, as if the deposit above was missed.
+    await seedStats(Array(7).fill(new BN(0)));
+
+    await sleep(3);
+    await program.methods
+      .claim()
+      .accounts({
+        ownerCheckpoints: aliceCheckpoints,
+        stats,
+        ownerUserAccount: aliceUserAccount,
+        owner: alice.publicKey,
+        claimTicket: claimTicketOf(position),
+        rentPayer: alice.publicKey,
+        positionHistory: positionHistory(position),
+        authority,
+        gsrmMint: GSRM_MINT,
+        ownerGsrmAccount: aliceGSRMAccount.publicKey,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([alice])
+      .rpc();
+
+    const statsAccount = await program.account.stats.fetch(stats);
+    expect(statsAccount.gsrmUnclaimed.toNumber()).to.equal(0);
+    expect(statsAccount.underflows.toNumber()).to.equal(1);
+
+    try {
+      await auditVaults();
+      assert(false);
+    } catch (e) {
+      if (e instanceof AnchorError) {
+        expect(e.error.errorCode.code).to.equal("StatsDrift");
+      } else assert(false);
+    }
+
+    // Seeding the right totals again clears the drift.
+    await seedStats([
+      before.srmLocked,
+      before.msrmLocked,
+      before.srmVesting,
+      before.msrmVesting,
+      before.gsrmUnclaimed.sub(new BN(100_000_000)),
+      before.srmRedeemable,
+      before.msrmRedeemable,
+    ]);
+    await auditVaults();
+
+    // Seeding is recorded like any other change by the config authority.
+    const history = await program.account.configHistory.fetch(configHistory);
+    const lastChange =
+      history.changes[(history.changeCount.toNumber() - 1) % 64];
+    expect(lastChange.field.statsUnderflows).to.not.be.undefined;
+    expect(lastChange.oldValue.u64[0].toNumber()).to.equal(1);
+    expect(lastChange.newValue.u64[0].toNumber()).to.equal(0);
+    expect(lastChange.signer.toBase58()).to.equal(alice.publicKey.toBase58());
+  });
+
+  it("can update lock tiers", async () => {
+    await program.methods
+      .updateLockTiers([
+        { duration: new BN(100), multiplier: new BN(15_000) },
+        { duration: new BN(200), multiplier: new BN(30_000) },
+      ])
+      .accounts({
+        config,
+        configAuthority: alice.publicKey,
+        configHistory,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([alice])
+      .rpc();
+
+    const configAccount = await program.account.config.fetch(config);
+    expect(configAccount.lockTiers[1].duration.toNumber()).to.equal(200);
+    expect(configAccount.lockTiers[1].multiplier.toNumber()).to.equal(30_000);
+    expect(configAccount.lockTiers[2].duration.toNumber()).to.equal(0);
+    expect(configAccount.maxLockMultiplier.toNumber()).to.equal(30_000);
+  });
+
+  it("max lock multiplier doesnt go down with lock tiers", async () => {
+    const updateLockTiers = (lockTiers) =>
+      program.methods
+        .updateLockTiers(lockTiers)
+        .accounts({
+          config,
+          configAuthority: alice.publicKey,
+          configHistory,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([alice])
+        .rpc();
+
+    const lockTiers = [
+      { duration: new BN(100), multiplier: new BN(15_000) },
+      { duration: new BN(200), multiplier: new BN(30_000) },
+    ];
+
+    await updateLockTiers([lockTiers[0]]);
+
+    const configAccount = await program.account.config.fetch(config);
+    expect(configAccount.lockTiers[1].duration.toNumber()).to.equal(0);
+    expect(configAccount.maxLockMultiplier.toNumber()).to.equal(30_000);
+
+    await updateLockTiers(lockTiers);
+  });
+
+  const depositLockedSrmForTier = async (lockTier: number) => {
+    const aliceAccount = await program.account.user.fetch(aliceUserAccount);
+    const [aliceLockedAccount] = findProgramAddressSync(
+      [
+        Buffer.from("locked_account"),
+        alice.publicKey.toBuffer(),
+        aliceAccount.lockIndex.toBuffer("le", 8),
+      ],
+      program.programId
+    );
+
+    const [claimTicket] = findProgramAddressSync(
+      [Buffer.from("claim_ticket"), aliceLockedAccount.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .depositLockedSrm(new BN(100_000_000), new BN(0), lockTier)
+      .accounts({
+        ownerCheckpoints: aliceCheckpoints,
+        stats,
+        payer: alice.publicKey,
+        owner: alice.publicKey,
+        ownerUserAccount: aliceUserAccount,
+        srmMint: SRM_MINT,
+        payerSrmAccount: aliceSRMAccount.publicKey,
+        authority,
+        config,
+        srmVault,
+        lockedAccount: aliceLockedAccount,
+        positionHistory: positionHistory(aliceLockedAccount),
+        claimTicket: claimTicket,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([alice])
+      .rpc();
+
+    return aliceLockedAccount;
+  };
+
+  it("can deposit srm for a lock tier", async () => {
+    const now = await connection.getBlockTime(await connection.getSlot());
+
+    const aliceLockedAccount = await depositLockedSrmForTier(2);
+
+    const lockedAccount = await program.account.lockedAccount.fetch(
+      aliceLockedAccount
+    );
+    expect(lockedAccount.lockTier).to.equal(2);
+    expect(lockedAccount.lockMultiplier.toNumber()).to.equal(30_000);
+    expect(lockedAccount.lockupEnd.toNumber()).to.be.within(
+      now + 200,
+      now + 210
+    );
+
+    // The tier multiplier applies in full, without decaying.
+    const weight = await aliceWeightOf([aliceLockedAccount]);
+    expect(weight.toNumber()).to.equal(300_000_000);
+  });
+
+  it("cant deposit srm for a missing lock tier", async () => {
+    try {
+      await depositLockedSrmForTier(3);
+      assert(false);
+    } catch (e) {
+      if (e instanceof AnchorError) {
+        expect(e.error.errorCode.code).to.equal("InvalidLockTier");
+      } else assert(false);
+    }
+  });
+
+  const increaseLockedSrm = (lockedAccount: PublicKey, amount: number) => {
+    const [claimTicket] = findProgramAddressSync(
+      [Buffer.from("claim_ticket"), lockedAccount.toBuffer()],
+      program.programId
+    );
+
+    return program.methods
+      .increaseLockedSrm(new BN(amount))
+      .accounts({
+        ownerCheckpoints: aliceCheckpoints,
+        stats,
+        payer: alice.publicKey,
+        owner: alice.publicKey,
+        ownerUserAccount: aliceUserAccount,
+        srmMint: SRM_MINT,
+        payerSrmAccount: aliceSRMAccount.publicKey,
+        authority,
+        config,
+        srmVault,
+        lockedAccount,
+        positionHistory: positionHistory(lockedAccount),
+        claimTicket,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([alice])
+      .rpc();
+  };
+
+  it("can increase locked srm", async () => {
+    const lockedAccounts = await program.account.lockedAccount.all([
+      {
+        memcmp: {
+          offset: 8,
+          bytes: alice.publicKey.toBase58(),
+        },
+      },
+    ]);
+    const claimed = await Promise.all(
+      lockedAccounts.map(async (a) => {
+        const [claimTicket] = findProgramAddressSync(
+          [Buffer.from("claim_ticket"), a.publicKey.toBuffer()],
+          program.programId
+        );
+        return (await connection.getAccountInfo(claimTicket)) === null;
+      })
+    );
+    const position = lockedAccounts.find(
+      (a, i) =>
+        claimed[i] && !a.account.isMsrm && a.account.status.active !== undefined
+    );
+    expect(position).to.not.be.undefined;
+
+    await increaseLockedSrm(position.publicKey, 50_000_000);
+
+    const lockedAccount = await program.account.lockedAccount.fetch(
+      position.publicKey
+    );
+    expect(lockedAccount.totalGsrmAmount.toNumber()).to.equal(
+      position.account.totalGsrmAmount.toNumber() + 50_000_000
+    );
+    expect(lockedAccount.lockIndex.toNumber()).to.equal(
+      position.account.lockIndex.toNumber()
+    );
+
+    const [claimTicket] = findProgramAddressSync(
+      [Buffer.from("claim_ticket"), position.publicKey.toBuffer()],
+      program.programId
+    );
+    const claimTicketAccount = await program.account.claimTicket.fetch(
+      claimTicket
+    );
+    expect(claimTicketAccount.gsrmAmount.toNumber()).to.equal(50_000_000);
+
+    // Previous top up hasn't been claimed yet.
+    let failed = false;
+    try {
+      await increaseLockedSrm(position.publicKey, 50_000_000);
+    } catch (e) {
+      failed = true;
+    }
+    expect(failed).to.be.true;
+  });
+
+  it("cant increase locked srm of another owner", async () => {
+    const [position] = await program.account.lockedAccount.all([
+      {
+        memcmp: {
+          offset: 8,
+          bytes: alice.publicKey.toBase58(),
+        },
+      },
+    ]);
+    const [claimTicket] = findProgramAddressSync(
+      [Buffer.from("claim_ticket"), position.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const ix = await program.methods
+      .increaseLockedSrm(new BN(1))
+      .accounts({
+        ownerCheckpoints: aliceCheckpoints,
+        stats,
+        payer: sbf.publicKey,
+        owner: alice.publicKey,
+        ownerUserAccount: aliceUserAccount,
+        srmMint: SRM_MINT,
+        payerSrmAccount: sbfSrmAccount,
+        authority,
+        config,
+        srmVault,
+        lockedAccount: position.publicKey,
+        positionHistory: positionHistory(position.publicKey),
+        claimTicket,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+    // Only sbf signs, as a griefer would.
+    ix.keys
+      .filter((key) => key.pubkey.equals(alice.publicKey))
+      .forEach((key) => (key.isSigner = false));
+
+    let logs: string[] = [];
+    try {
+      await provider.sendAndConfirm(new Transaction().add(ix), [sbf]);
+    } catch (e) {
+      logs = e.logs ?? [];
+    }
+    expect(logs.join("\n")).to.contain("AccountNotSigner");
+  });
+
+  const extendLock = (lockedAccount: PublicKey, lockupEnd: number) =>
+    program.methods
+      .extendLock(new BN(lockupEnd))
+      .accounts({
+        owner: alice.publicKey,
+        config,
+        lockedAccount,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      })
+      .signers([alice])
+      .rpc();
+
+  it("can extend lock", async () => {
+    const [aliceLockedAccount] = findProgramAddressSync(
+      [
+        Buffer.from("locked_account"),
+        alice.publicKey.toBuffer(),
+        new BN(2).toBuffer("le", 8),
+      ],
+      program.programId
+    );
+    const { lockupEnd } = await program.account.lockedAccount.fetch(
+      aliceLockedAccount
+    );
+    const now = await connection.getBlockTime(await connection.getSlot());
+    const newLockupEnd = Math.max(lockupEnd.toNumber(), now) + 100;
+
+    const weight = await aliceWeightOf([aliceLockedAccount]);
+    await extendLock(aliceLockedAccount, newLockupEnd);
+    const extendedWeight = await aliceWeightOf([aliceLockedAccount]);
+
+    const lockedAccount = await program.account.lockedAccount.fetch(
+      aliceLockedAccount
+    );
+    expect(lockedAccount.lockupEnd.toNumber()).to.equal(newLockupEnd);
+
+    // 100s more of lockup adds a tenth of the unburned gSRM, less what
+    // decayed in between.
+    const amount = lockedAccount.totalGsrmAmount
+      .sub(lockedAccount.gsrmBurned)
+      .toNumber();
+    expect(extendedWeight.sub(weight).toNumber()).to.be.within(
+      amount / 10 - (amount / 1000) * 5,
+      amount / 10 + 1
+    );
+
+    try {
+      await extendLock(aliceLockedAccount, newLockupEnd - 1);
+      assert(false);
+    } catch (e) {
+      if (e instanceof AnchorError) {
+        expect(e.error.errorCode.code).to.equal("InvalidLockupEnd");
+      } else assert(false);
+    }
+  });
+
+  it("cant extend lock past max lockup period", async () => {
+    const [aliceLockedAccount] = findProgramAddressSync(
+      [
+        Buffer.from("locked_account"),
+        alice.publicKey.toBuffer(),
+        new BN(2).toBuffer("le", 8),
+      ],
+      program.programId
+    );
+    const now = await connection.getBlockTime(await connection.getSlot());
+
+    try {
+      await extendLock(aliceLockedAccount, now + 2000);
+      assert(false);
+    } catch (e) {
+      if (e instanceof AnchorError) {
+        expect(e.error.errorCode.code).to.equal("InvalidLockupEnd");
+      } else assert(false);
+    }
+  });
+
+  const claimPosition = (position: PublicKey) =>
+    program.methods
+      .claim()
+      .accounts({
+        ownerCheckpoints: aliceCheckpoints,
+        stats,
+        ownerUserAccount: aliceUserAccount,
+        owner: alice.publicKey,
+        claimTicket: claimTicketOf(position),
+        rentPayer: alice.publicKey,
+        positionHistory: positionHistory(position),
+        authority,
+        gsrmMint: GSRM_MINT,
+        ownerGsrmAccount: aliceGSRMAccount.publicKey,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([alice])
+      .rpc();
+
+  let mergedLockedAccount: PublicKey;
+
+  it("can merge locked accounts", async () => {
+    const target = await depositLockedSrmForTier(0);
+    mergedLockedAccount = target;
+    const source = await depositLockedSrmForTier(0);
+
+    // Unclaimed ClaimTicket
+    try {
+      await program.methods
+        .mergeLockedAccounts()
+        .accounts({
+          owner: alice.publicKey,
+          ownerUserAccount: aliceUserAccount,
+          lockedAccount: target,
+          positionHistory: positionHistory(target),
+          clock: SYSVAR_CLOCK_PUBKEY,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(
+          [
+            source,
+            claimTicketOf(source),
+            alice.publicKey,
+            positionHistory(source),
+          ].map((pubkey) => ({
+            pubkey,
+            isWritable: true,
+            isSigner: false,
+          }))
+        )
+        .signers([alice])
+        .rpc();
+      assert(false);
+    } catch (e) {
+      if (e instanceof AnchorError) {
+        expect(e.error.errorCode.code).to.equal("UnsettledTickets");
+      } else assert(false);
+    }
+
+    await sleep(3);
+    await program.methods
+      .claim()
+      .accounts({
+        ownerCheckpoints: aliceCheckpoints,
+        stats,
+        ownerUserAccount: aliceUserAccount,
+        owner: alice.publicKey,
+        claimTicket: claimTicketOf(source),
+        rentPayer: alice.publicKey,
+        positionHistory: positionHistory(source),
+        authority,
+        gsrmMint: GSRM_MINT,
+        ownerGsrmAccount: aliceGSRMAccount.publicKey,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([alice])
+      .rpc();
+
+    const userBefore = await program.account.user.fetch(aliceUserAccount);
+
+    await program.methods
+      .mergeLockedAccounts()
+      .accounts({
+        owner: alice.publicKey,
+        ownerUserAccount: aliceUserAccount,
+        lockedAccount: target,
+        positionHistory: positionHistory(target),
+        clock: SYSVAR_CLOCK_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(
+        [
+          source,
+          claimTicketOf(source),
+          alice.publicKey,
+          positionHistory(source),
+        ].map((pubkey) => ({
+          pubkey,
+          isWritable: true,
+          isSigner: false,
+        }))
+      )
+      .signers([alice])
+      .rpc();
+
+    const lockedAccount = await program.account.lockedAccount.fetch(target);
+    expect(lockedAccount.totalGsrmAmount.toNumber()).to.equal(200_000_000);
+    expect(await program.account.lockedAccount.fetchNullable(source)).to.be
+      .null;
+    expect(await connection.getAccountInfo(positionHistory(source))).to.be
+      .null;
+
+    const userAfter = await program.account.user.fetch(aliceUserAccount);
+    expect(userAfter.closedPositions.toNumber()).to.equal(
+      userBefore.closedPositions.toNumber() + 1
+    );
+    expect(userAfter.srmLocked.toNumber()).to.equal(
+      userBefore.srmLocked.toNumber()
+    );
+  });
+
+  it("cant merge locked accounts with open redeem tickets", async () => {
+    const target = await depositLockedSrmForTier(0);
+    const source = await depositLockedSrmForTier(0);
+
+    await sleep(3);
+    await claimPosition(source);
+
+    const [redeemTicket] = findProgramAddressSync(
+      [
+        Buffer.from("redeem_ticket"),
+        source.toBuffer(),
+        new BN(0).toBuffer("le", 8),
+      ],
+      program.programId
+    );
+    await program.methods
+      .burnLockedGsrm(new BN(50_000_000))
+      .accounts({
+        ownerCheckpoints: aliceCheckpoints,
+        stats,
+        ownerUserAccount: aliceUserAccount,
+        owner: alice.publicKey,
+        authority,
+        config,
+        gsrmMint: GSRM_MINT,
+        ownerGsrmAccount: aliceGSRMAccount.publicKey,
+        lockedAccount: source,
+        positionHistory: positionHistory(source),
+        redeemTicket,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([alice])
+      .rpc();
+
+    const mergeLockedAccounts = (tickets: PublicKey[]) =>
+      program.methods
+        .mergeLockedAccounts()
+        .accounts({
+          owner: alice.publicKey,
+          ownerUserAccount: aliceUserAccount,
+          lockedAccount: target,
+          positionHistory: positionHistory(target),
+          clock: SYSVAR_CLOCK_PUBKEY,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(
+          [source, ...tickets, alice.publicKey, positionHistory(source)].map(
+            (pubkey) => ({
+              pubkey,
+              isWritable: true,
+              isSigner: false,
+            })
+          )
+        )
+        .signers([alice])
+        .rpc();
+
+    // RedeemTicket missing, then not redeemed yet.
+    for (const tickets of [
+      [claimTicketOf(source)],
+      [claimTicketOf(source), redeemTicket],
+    ]) {
+      try {
+        await mergeLockedAccounts(tickets);
+        assert(false);
+      } catch (e) {
+        if (e instanceof AnchorError) {
+          expect(e.error.errorCode.code).to.equal("UnsettledTickets");
+        } else assert(false);
+      }
+    }
+
+    await sleep(2);
+    await program.methods
+      .redeemSrm()
+      .accounts({
+        owner: alice.publicKey,
+        authority,
+        config,
+        stats,
+        ownerUserAccount: aliceUserAccount,
+        redeemTicket,
+        positionHistory: positionHistory(source),
+        srmMint: SRM_MINT,
+        srmVault,
+        ownerSrmAccount: aliceSRMAccount.publicKey,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([alice])
+      .rpc();
+
+    await mergeLockedAccounts([claimTicketOf(source), redeemTicket]);
+
+    const lockedAccount = await program.account.lockedAccount.fetch(target);
+    expect(lockedAccount.totalGsrmAmount.toNumber()).to.equal(200_000_000);
+    expect(lockedAccount.gsrmBurned.toNumber()).to.equal(50_000_000);
+    expect(await connection.getAccountInfo(positionHistory(source))).to.be
+      .null;
+  });
+
+  const splitLockedAccount = async (gsrmAmount: number) => {
+    const aliceAccount = await program.account.user.fetch(aliceUserAccount);
+    const [newLockedAccount] = findProgramAddressSync(
+      [
+        Buffer.from("locked_account"),
+        alice.publicKey.toBuffer(),
+        aliceAccount.lockIndex.toBuffer("le", 8),
+      ],
+      program.programId
+    );
+
+    await program.methods
+      .splitLockedAccount(new BN(gsrmAmount))
+      .accounts({
+        owner: alice.publicKey,
+        ownerUserAccount: aliceUserAccount,
+        lockedAccount: mergedLockedAccount,
+        newLockedAccount,
+        positionHistory: positionHistory(mergedLockedAccount),
+        newPositionHistory: positionHistory(newLockedAccount),
+        clock: SYSVAR_CLOCK_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([alice])
+      .rpc();
+
+    return newLockedAccount;
+  };
+
+  it("can split locked account", async () => {
+    const newLockedAccount = await splitLockedAccount(50_000_000);
+
+    const lockedAccount = await program.account.lockedAccount.fetch(
+      mergedLockedAccount
+    );
+    expect(lockedAccount.totalGsrmAmount.toNumber()).to.equal(150_000_000);
+
+    const newAccount = await program.account.lockedAccount.fetch(
+      newLockedAccount
+    );
+    expect(newAccount.totalGsrmAmount.toNumber()).to.equal(50_000_000);
+    expect(newAccount.gsrmBurned.toNumber()).to.equal(0);
+    expect(newAccount.isMsrm).to.be.false;
+    expect(newAccount.lockupEnd.toNumber()).to.equal(
+      lockedAccount.lockupEnd.toNumber()
+    );
+  });
+
+  it("cant split more than the unburned gsrm", async () => {
+    try {
+      await splitLockedAccount(200_000_000);
+      assert(false);
+    } catch (e) {
+      if (e instanceof AnchorError) {
+        expect(e.error.errorCode.code).to.equal("InvalidGSRMAmount");
+      } else assert(false);
+    }
+  });
+
+  const updateConfigParams = (
+    cliffPeriod: number,
+    linearVestingPeriod: number
+  ) =>
+    program.methods
+      .updateConfigParams(
+        new BN(2),
+        new BN(2),
+        new BN(cliffPeriod),
+        new BN(linearVestingPeriod)
+      )
+      .accounts({
+        config,
+        configAuthority: alice.publicKey,
+        configHistory,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([alice])
+      .rpc();
+
+  const depositVest = async (amount: BN, isMsrm: boolean) => {
+    const aliceAccount = await program.account.user.fetch(aliceUserAccount);
+    const [aliceVestAccount] = findProgramAddressSync(
+      [
+        Buffer.from("vest_account"),
+        alice.publicKey.toBuffer(),
+        aliceAccount.vestIndex.toBuffer("le", 8),
+      ],
+      program.programId
+    );
+
+    const accounts = {
+      ownerCheckpoints: aliceCheckpoints,
+      stats,
+      owner: alice.publicKey,
+      ownerUserAccount: aliceUserAccount,
+      vestAccount: aliceVestAccount,
+      positionHistory: positionHistory(aliceVestAccount),
+      claimTicket: claimTicketOf(aliceVestAccount),
+      authority,
+      config,
+      clock: SYSVAR_CLOCK_PUBKEY,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    };
+    if (isMsrm) {
+      await program.methods
+        .depositVestMsrm(amount)
+        .accounts({
+          ...accounts,
+          payer: sbf.publicKey,
+          msrmMint: MSRM_MINT,
+          payerMsrmAccount: sbfMsrmAccount,
+          msrmVault,
+        })
+        .signers([sbf])
+        .rpc();
+    } else {
+      await program.methods
+        .depositVestSrm(amount)
+        .accounts({
+          ...accounts,
+          payer: alice.publicKey,
+          srmMint: SRM_MINT,
+          payerSrmAccount: aliceSRMAccount.publicKey,
+          srmVault,
+        })
+        .signers([alice])
+        .rpc();
+    }
+
+    return aliceVestAccount;
+  };
+
+  const burnVestGsrm = async (vestAccount: PublicKey, amount: BN) => {
+    const [redeemTicket] = findProgramAddressSync(
+      [
+        Buffer.from("redeem_ticket"),
+        vestAccount.toBuffer(),
+        new BN(0).toBuffer("le", 8),
+      ],
+      program.programId
+    );
+
+    await program.methods
+      .burnVestGsrm(amount)
+      .accounts({
+        owner: alice.publicKey,
+        authority,
+        config,
+        stats,
+        ownerUserAccount: aliceUserAccount,
+        gsrmMint: GSRM_MINT,
+        ownerGsrmAccount: aliceGSRMAccount.publicKey,
+        vestAccount,
+        ownerCheckpoints: aliceCheckpoints,
+        redeemTicket,
+        positionHistory: positionHistory(vestAccount),
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([alice])
+      .rpc();
+
+    return redeemTicket;
+  };
+
+  it("burns only the vested gsrm of vest accounts", async () => {
+    // No cliff and a short vesting period, so both positions are partly vested.
+    await updateConfigParams(0, 30);
+
+    const srmTotal = new BN(1000 * 1000000);
+    const msrmTotal = new BN(3).mul(new BN(MSRM_MULTIPLIER));
+    const srmVestAccount = await depositVest(srmTotal, false);
+    const msrmVestAccount = await depositVest(new BN(3), true);
+
+    await sleep(3);
+    await claimPosition(srmVestAccount);
+    await claimPosition(msrmVestAccount);
+
+    // Over 1 MSRM vested, but not all of it.
+    await sleep(10);
+
+    const gsrmBefore = await connection.getTokenAccountBalance(
+      aliceGSRMAccount.publicKey
+    );
+    const srmRedeemTicket = await burnVestGsrm(srmVestAccount, srmTotal);
+    const gsrmAfter = await connection.getTokenAccountBalance(
+      aliceGSRMAccount.publicKey
+    );
+
+    const srmVest = await program.account.vestAccount.fetch(srmVestAccount);
+    expect(srmVest.gsrmBurned.gtn(0)).to.be.true;
+    expect(srmVest.gsrmBurned.lt(srmTotal)).to.be.true;
+    expect(
+      new BN(gsrmBefore.value.amount)
+        .sub(new BN(gsrmAfter.value.amount))
+        .toString()
+    ).to.equal(srmVest.gsrmBurned.toString());
+    const srmTicket = await program.account.redeemTicket.fetch(
+      srmRedeemTicket
+    );
+    expect(srmTicket.amount.toString()).to.equal(
+      srmVest.gsrmBurned.toString()
+    );
+
+    const msrmRedeemTicket = await burnVestGsrm(msrmVestAccount, msrmTotal);
+
+    // Only whole MSRM are burned, the rest stays vested.
+    const msrmVest = await program.account.vestAccount.fetch(msrmVestAccount);
+    expect(msrmVest.gsrmBurned.toString()).to.equal(
+      new BN(MSRM_MULTIPLIER).toString()
+    );
+    const msrmTicket = await program.account.redeemTicket.fetch(
+      msrmRedeemTicket
+    );
+    expect(msrmTicket.isMsrm).to.be.true;
+    expect(msrmTicket.amount.toNumber()).to.equal(1);
+
+    await updateConfigParams(12, 500);
+  });
+
+  it("discounts unvested gsrm in voter weight", async () => {
+    await updateConfigParams(0, 20);
+
+    const total = 1000 * 1000000;
+    const vestAccount = await depositVest(new BN(total), false);
+    const { createdAt } = await program.account.vestAccount.fetch(vestAccount);
+
+    await sleep(5);
+    const before = await connection.getBlockTime(await connection.getSlot());
+    const weight = await aliceWeightOf([vestAccount]);
+    const after = await connection.getBlockTime(await connection.getSlot());
+
+    // Vested gSRM counts in full, unvested gSRM at the unvested weight of 50%.
+    const expectedWeight = (now: number) => {
+      const vested = Math.floor(
+        (total * Math.min(now - createdAt.toNumber(), 20)) / 20
+      );
+      return vested + Math.floor(((total - vested) * 5_000) / 10_000);
+    };
+    expect(weight.toNumber()).to.be.within(
+      expectedWeight(before - 1),
+      expectedWeight(after + 1)
+    );
+    expect(weight.toNumber()).to.be.greaterThan(total / 2);
+    expect(weight.toNumber()).to.be.lessThan(total);
+
+    await updateConfigParams(12, 500);
+  });
+
+  it("late top ups relock lock tier positions for their full term", async () => {
+    const updateLockTiers = (lockTiers) =>
+      program.methods
+        .updateLockTiers(lockTiers)
+        .accounts({
+          config,
+          configAuthority: alice.publicKey,
+          configHistory,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([alice])
+        .rpc();
+
+    const lockTiers = [
+      { duration: new BN(100), multiplier: new BN(15_000) },
+      { duration: new BN(200), multiplier: new BN(30_000) },
+    ];
+    await updateLockTiers([
+      ...lockTiers,
+      { duration: new BN(12), multiplier: new BN(20_000) },
+    ]);
+
+    const position = await depositLockedSrmForTier(3);
+    const { lockupEnd } = await program.account.lockedAccount.fetch(position);
+
+    await sleep(6);
+    await claimPosition(position);
+
+    const now = await connection.getBlockTime(await connection.getSlot());
+    await increaseLockedSrm(position, 100_000_000);
+
+    const lockedAccount = await program.account.lockedAccount.fetch(position);
+    expect(lockedAccount.lockupEnd.toNumber()).to.be.within(
+      now + 12,
+      now + 17
+    );
+    expect(lockedAccount.lockupEnd.gt(lockupEnd)).to.be.true;
+
+    // Past the old lockup end, the whole position keeps the tier multiplier.
+    await sleep(lockupEnd.toNumber() - now + 1);
+    const weight = await aliceWeightOf([position]);
+    expect(weight.toNumber()).to.equal(400_000_000);
+
+    await updateLockTiers(lockTiers);
+  });
+
   // =======================================================================
 
   // it("can burn vest gsrm", async () => {